@@ -1,44 +1,78 @@
-use crate::config::load_config;
+use crate::config::Config;
+use crate::control::{self, ControlRequest};
 use crate::logger::Logger;
+use crate::logview::LogViewer;
+use crate::theme;
+use crate::widgets::network::NetworkWidget;
 use crate::widgets::sysinfo::SysInfoWidget;
 use crate::widgets::{
     GJWidget, clock::ClockWidget, weather::WeatherWidget, workspaces::WorkspacesWidget,
 };
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind,
+};
 use ratatui::DefaultTerminal;
+use ratatui::Frame;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Style, Stylize};
+use ratatui::widgets::{Clear, Paragraph};
+use std::sync::mpsc::Receiver;
 use std::time::{Duration, Instant};
 
+type WidgetSlot = (Box<dyn GJWidget>, Duration, Instant);
+
 #[derive(Debug, Default)]
 pub struct App {
     exit: bool,
+    focused: Option<usize>,
+    areas: Vec<Rect>,
+    log_viewer: Option<LogViewer>,
 }
 
 impl App {
     pub fn run_app(
         &mut self,
         terminal: &mut DefaultTerminal,
+        config: Config,
         logger: &'static Logger,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        let config = load_config("gjwidgets.toml");
+        let control = control::spawn_server(logger.for_target("control"));
 
-        let mut widgets: Vec<(Box<dyn GJWidget>, Duration, Instant)> = vec![
+        let mut widgets: Vec<WidgetSlot> = vec![
             (
-                Box::new(ClockWidget::new(config.clock)),
+                Box::new(ClockWidget::new(config.clock, logger.for_target("clock"))),
                 Duration::from_secs(1),
                 Instant::now(),
             ),
             (
-                Box::new(WeatherWidget::new(config.weather, logger)),
+                Box::new(WeatherWidget::new(
+                    config.weather,
+                    logger.for_target("weather"),
+                )),
                 Duration::from_secs(3600),
                 Instant::now(),
             ),
             (
-                Box::new(WorkspacesWidget::new(config.workspaces, logger)),
+                Box::new(WorkspacesWidget::new(
+                    config.workspaces,
+                    logger.for_target("workspaces"),
+                )),
                 Duration::from_millis(100),
                 Instant::now(),
             ),
             (
-                Box::new(SysInfoWidget::new(logger)),
+                Box::new(SysInfoWidget::new(
+                    config.sysinfo,
+                    logger.for_target("sysinfo"),
+                )),
+                Duration::from_secs(2),
+                Instant::now(),
+            ),
+            (
+                Box::new(NetworkWidget::new(
+                    config.network,
+                    logger.for_target("network"),
+                )),
                 Duration::from_secs(2),
                 Instant::now(),
             ),
@@ -62,15 +96,25 @@ impl App {
                         ratatui::layout::Constraint::Max(3),
                         ratatui::layout::Constraint::Min(19),
                         ratatui::layout::Constraint::Min(20),
+                        ratatui::layout::Constraint::Min(6),
                     ])
                     .split(size);
+                self.areas = chunks.to_vec();
 
-                for ((widget, _, _), area) in widgets.iter().zip(chunks.iter()) {
+                for (index, ((widget, _, _), area)) in widgets.iter().zip(chunks.iter()).enumerate()
+                {
                     widget.render(f, *area);
+                    if let Some(error) = widget.error() {
+                        render_error_badge(f, *area, &error, self.focused == Some(index));
+                    }
+                }
+                if let Some(viewer) = &self.log_viewer {
+                    viewer.render(f, size, logger);
                 }
             })?;
 
-            self.handle_events(logger)?;
+            self.handle_control_requests(&control.requests, &mut widgets, logger);
+            self.handle_events(&mut widgets, logger)?;
             init = false;
             std::thread::sleep(Duration::from_millis(20)); // Slightly faster sleep
         }
@@ -78,27 +122,128 @@ impl App {
         Ok(())
     }
 
-    fn handle_events(&mut self, logger: &Logger) -> Result<(), Box<dyn std::error::Error>> {
+    fn handle_control_requests(
+        &mut self,
+        control_rx: &Receiver<ControlRequest>,
+        widgets: &mut [WidgetSlot],
+        logger: &Logger,
+    ) {
+        while let Ok(request) = control_rx.try_recv() {
+            let args: Vec<&str> = request.args.iter().map(String::as_str).collect();
+            logger.debug(&format!("Control command: {}", args.join(" ")));
+            let reply = match args.as_slice() {
+                ["quit"] => {
+                    self.exit(logger);
+                    "ok".to_string()
+                }
+                _ => widgets
+                    .iter_mut()
+                    .find_map(|(widget, _, _)| widget.handle_command(&args))
+                    .unwrap_or_else(|| format!("error: unknown command '{}'", args.join(" "))),
+            };
+            let _ = request.reply.send(reply);
+        }
+    }
+
+    fn handle_events(
+        &mut self,
+        widgets: &mut [WidgetSlot],
+        logger: &Logger,
+    ) -> Result<(), Box<dyn std::error::Error>> {
         if event::poll(Duration::from_millis(50))? {
             match event::read()? {
                 Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                    self.handle_key_event(key_event, logger)
+                    self.handle_key_event(key_event, widgets, logger)
                 }
+                Event::Mouse(mouse_event) => self.handle_mouse_event(mouse_event, widgets),
                 _ => {}
             };
         }
         Ok(())
     }
 
-    fn handle_key_event(&mut self, key_event: KeyEvent, logger: &Logger) {
+    fn handle_key_event(
+        &mut self,
+        key_event: KeyEvent,
+        widgets: &mut [WidgetSlot],
+        logger: &Logger,
+    ) {
+        if let Some(viewer) = &mut self.log_viewer {
+            if !viewer.handle_key_event(key_event, logger) {
+                self.log_viewer = None;
+            }
+            return;
+        }
+        if let Some((widget, _, _)) = self.focused.and_then(|i| widgets.get_mut(i))
+            && widget.handle_key_event(key_event)
+        {
+            return;
+        }
+
         match key_event.code {
             KeyCode::Char('q') => self.exit(logger),
+            KeyCode::Char('L') => self.log_viewer = Some(LogViewer::new()),
+            KeyCode::Tab => {
+                let next = self.focused.map_or(0, |i| (i + 1) % widgets.len());
+                self.set_focus(Some(next), widgets);
+            }
+            KeyCode::BackTab => {
+                let prev = self.focused.map_or(widgets.len() - 1, |i| {
+                    (i + widgets.len() - 1) % widgets.len()
+                });
+                self.set_focus(Some(prev), widgets);
+            }
+            KeyCode::Esc => self.set_focus(None, widgets),
             _ => {}
         }
     }
 
+    fn handle_mouse_event(&mut self, mouse_event: MouseEvent, widgets: &mut [WidgetSlot]) {
+        if mouse_event.kind != MouseEventKind::Down(MouseButton::Left) {
+            return;
+        }
+        let position = Position::new(mouse_event.column, mouse_event.row);
+        if let Some(index) = self.areas.iter().position(|area| area.contains(position)) {
+            self.set_focus(Some(index), widgets);
+            if let Some((widget, _, _)) = widgets.get_mut(index) {
+                widget.handle_click();
+            }
+        }
+    }
+
+    fn set_focus(&mut self, focused: Option<usize>, widgets: &mut [WidgetSlot]) {
+        if let Some((widget, _, _)) = self.focused.and_then(|i| widgets.get_mut(i)) {
+            widget.set_focused(false);
+        }
+        if let Some((widget, _, _)) = focused.and_then(|i| widgets.get_mut(i)) {
+            widget.set_focused(true);
+        }
+        self.focused = focused;
+    }
+
     fn exit(&mut self, logger: &Logger) {
         logger.info("Exiting application");
         self.exit = true;
     }
 }
+
+/// Draws a red "!" in the top right corner of a failing widget, with the
+/// message next to it while the widget is focused.
+fn render_error_badge(f: &mut Frame, area: Rect, error: &str, focused: bool) {
+    let theme = theme::current();
+    let text = if focused {
+        format!(" ! {} ", error.lines().next().unwrap_or_default())
+    } else {
+        " ! ".to_string()
+    };
+    let width = (text.chars().count() as u16).min(area.width);
+    if width == 0 || area.height == 0 {
+        return;
+    }
+    let badge = Rect::new(area.right() - width, area.y, width, 1);
+    f.render_widget(Clear, badge);
+    f.render_widget(
+        Paragraph::new(text).style(Style::new().fg(theme.fg).bg(theme.critical).bold()),
+        badge,
+    );
+}
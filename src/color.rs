@@ -0,0 +1,81 @@
+use ratatui::style::Color;
+use std::str::FromStr;
+
+/// Parses a color name ("blue", "darkgray") or hex value ("#89b4fa").
+pub fn parse_color(value: &str) -> Option<Color> {
+    Color::from_str(value.trim()).ok()
+}
+
+/// RGB components for a color, using xterm defaults for the named palette.
+pub fn to_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+        Color::Black => Some((0, 0, 0)),
+        Color::Red => Some((205, 0, 0)),
+        Color::Green => Some((0, 205, 0)),
+        Color::Yellow => Some((205, 205, 0)),
+        Color::Blue => Some((0, 0, 238)),
+        Color::Magenta => Some((205, 0, 205)),
+        Color::Cyan => Some((0, 205, 205)),
+        Color::Gray => Some((229, 229, 229)),
+        Color::DarkGray => Some((127, 127, 127)),
+        Color::LightRed => Some((255, 0, 0)),
+        Color::LightGreen => Some((0, 255, 0)),
+        Color::LightYellow => Some((255, 255, 0)),
+        Color::LightBlue => Some((92, 92, 255)),
+        Color::LightMagenta => Some((255, 0, 255)),
+        Color::LightCyan => Some((0, 255, 255)),
+        Color::White => Some((255, 255, 255)),
+        _ => None,
+    }
+}
+
+/// Evenly spaced color stops interpolated in RGB space.
+#[derive(Debug, Clone)]
+pub struct Gradient {
+    stops: Vec<Color>,
+}
+
+impl Gradient {
+    pub fn new(stops: Vec<Color>) -> Self {
+        Self { stops }
+    }
+
+    /// Builds a gradient from config strings, skipping values that do not parse.
+    pub fn from_strings(values: &[String]) -> Self {
+        Self::new(values.iter().filter_map(|v| parse_color(v)).collect())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stops.is_empty()
+    }
+
+    /// Color at position `t` in `0.0..=1.0`.
+    pub fn at(&self, t: f32) -> Color {
+        match self.stops.len() {
+            0 => Color::Reset,
+            1 => self.stops[0],
+            len => {
+                let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+                let scaled = t * (len - 1) as f32;
+                let index = (scaled.floor() as usize).min(len - 2);
+                let local = scaled - index as f32;
+                let (from, to) = (self.stops[index], self.stops[index + 1]);
+                match (to_rgb(from), to_rgb(to)) {
+                    (Some(a), Some(b)) => Color::Rgb(
+                        lerp(a.0, b.0, local),
+                        lerp(a.1, b.1, local),
+                        lerp(a.2, b.2, local),
+                    ),
+                    // Indexed or reset colors can't be blended, pick the nearest stop
+                    _ if local < 0.5 => from,
+                    _ => to,
+                }
+            }
+        }
+    }
+}
+
+fn lerp(a: u8, b: u8, t: f32) -> u8 {
+    (a as f32 + (b as f32 - a as f32) * t).round() as u8
+}
@@ -1,25 +1,395 @@
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::fs;
 
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GradientDirection {
+    /// Top to bottom across glyph rows.
+    #[default]
+    Vertical,
+    /// Left to right across glyph columns.
+    Horizontal,
+}
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TextEffect {
+    #[default]
+    None,
+    Shadow,
+    Outline,
+}
 #[derive(Debug, Deserialize)]
 pub struct ClockConfig {
     pub time_format: String,
     pub date_format: String,
     pub time_font: String,
     pub date_font: String,
+    /// Color stops across the time text, e.g. ["#89b4fa", "#cba6f7"].
+    #[serde(default)]
+    pub time_gradient: Vec<String>,
+    #[serde(default)]
+    pub time_gradient_direction: GradientDirection,
+    /// Colors cycled per character, overriding the gradient.
+    #[serde(default)]
+    pub time_colors: Vec<String>,
+    #[serde(default)]
+    pub time_effect: TextEffect,
+    #[serde(default)]
+    pub date_gradient: Vec<String>,
+    #[serde(default)]
+    pub date_gradient_direction: GradientDirection,
+    #[serde(default)]
+    pub date_colors: Vec<String>,
+    #[serde(default)]
+    pub date_effect: TextEffect,
+    /// IANA zone name, e.g. "Europe/Helsinki". Local time when unset.
+    #[serde(default)]
+    pub timezone: Option<String>,
+    #[serde(default = "default_world_format")]
+    pub world_format: String,
+    #[serde(default)]
+    pub world_clock: Vec<WorldClockConfig>,
+    #[serde(default)]
+    pub calendar: CalendarConfig,
+    #[serde(default)]
+    pub timer: TimerConfig,
+    #[serde(default)]
+    pub alarms: Vec<AlarmConfig>,
+    /// How long a due alarm flashes the clock unless dismissed.
+    #[serde(default = "default_alarm_flash_secs")]
+    pub alarm_flash_secs: u64,
+}
+#[derive(Debug, Deserialize, Clone)]
+pub struct AlarmConfig {
+    /// "HH:MM" or "HH:MM:SS"
+    pub time: String,
+    /// Weekday names ("mon", "tuesday"), every day when empty.
+    #[serde(default)]
+    pub weekdays: Vec<String>,
+    #[serde(default)]
+    pub label: String,
+    /// Run through `sh -c` when the alarm fires.
+    #[serde(default)]
+    pub command: Option<String>,
+    #[serde(default = "default_true")]
+    pub notify: bool,
+}
+
+fn default_alarm_flash_secs() -> u64 {
+    60
+}
+
+fn default_true() -> bool {
+    true
+}
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct TimerConfig {
+    /// Countdown length used by the `t` key and `clock timer` without an argument.
+    pub default_timer: String,
+    pub pomodoro_work: String,
+    pub pomodoro_break: String,
+    pub pomodoro_long_break: String,
+    /// Work sessions before a long break.
+    pub pomodoro_sessions: u32,
+    pub notify: bool,
+}
+
+impl Default for TimerConfig {
+    fn default() -> Self {
+        Self {
+            default_timer: "5m".to_string(),
+            pomodoro_work: "25m".to_string(),
+            pomodoro_break: "5m".to_string(),
+            pomodoro_long_break: "15m".to_string(),
+            pomodoro_sessions: 4,
+            notify: true,
+        }
+    }
+}
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct CalendarConfig {
+    pub week_start: String,
+    pub week_numbers: bool,
+    /// `.ics` file or vdir directory to read events from.
+    pub events: Option<String>,
+    /// Show the calendar whenever the clock has focus, not only after a click.
+    pub show_on_focus: bool,
+}
+
+impl Default for CalendarConfig {
+    fn default() -> Self {
+        Self {
+            week_start: "monday".to_string(),
+            week_numbers: true,
+            events: None,
+            show_on_focus: false,
+        }
+    }
+}
+#[derive(Debug, Deserialize, Clone)]
+pub struct WorldClockConfig {
+    pub label: String,
+    pub timezone: String,
+}
+
+fn default_world_format() -> String {
+    "%H:%M".to_string()
+}
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum IconStyle {
+    #[default]
+    Nerd,
+    Emoji,
+    Ascii,
 }
 #[derive(Debug, Deserialize)]
 pub struct WeatherConfig {
     pub font: String,
     pub location: String,
+    #[serde(default)]
+    pub icons: IconStyle,
+    #[serde(default = "default_temp_gradient")]
+    pub temp_gradient: Vec<String>,
+    #[serde(default = "default_temp_range")]
+    pub temp_range: (f32, f32),
+}
+
+fn default_temp_gradient() -> Vec<String> {
+    vec![
+        "#5e81ac".to_string(),
+        "#88c0d0".to_string(),
+        "#ebcb8b".to_string(),
+        "#bf616a".to_string(),
+    ]
+}
+
+fn default_temp_range() -> (f32, f32) {
+    (-20.0, 30.0)
 }
 #[derive(Debug, Deserialize, Clone)]
 pub struct WorkspacesConfig {}
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SysInfoView {
+    #[default]
+    Bars,
+    Graphs,
+}
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CoreView {
+    #[default]
+    Heatmap,
+    Bars,
+}
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProcessSort {
+    #[default]
+    Cpu,
+    Memory,
+}
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ThresholdConfig {
+    pub warning: Option<u64>,
+    pub critical: Option<u64>,
+    /// How far a value must drop below a threshold before the level clears.
+    pub hysteresis: u64,
+    pub blink: bool,
+    /// Desktop notification when a threshold is crossed upwards.
+    pub notify: bool,
+    /// Shell command run on each upward crossing, with `GJPANEL_METRIC`,
+    /// `GJPANEL_VALUE` and `GJPANEL_LEVEL` set.
+    pub command: Option<String>,
+}
+
+impl Default for ThresholdConfig {
+    fn default() -> Self {
+        Self {
+            warning: None,
+            critical: None,
+            hysteresis: 5,
+            blink: false,
+            notify: false,
+            command: None,
+        }
+    }
+}
+
+fn default_thresholds() -> HashMap<String, ThresholdConfig> {
+    HashMap::from([
+        (
+            "cpu_temp".to_string(),
+            ThresholdConfig {
+                warning: Some(75),
+                critical: Some(85),
+                blink: true,
+                notify: true,
+                ..ThresholdConfig::default()
+            },
+        ),
+        (
+            "mem".to_string(),
+            ThresholdConfig {
+                warning: Some(80),
+                critical: Some(90),
+                ..ThresholdConfig::default()
+            },
+        ),
+    ])
+}
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct SysInfoConfig {
+    /// Samples kept per metric for the graph view, one per poll (2s).
+    pub history_length: usize,
+    pub view: SysInfoView,
+    /// Show the per-core panel on startup, toggled with `c`.
+    pub show_cores: bool,
+    pub core_view: CoreView,
+    /// Component label or glob ("k10temp Tctl", "coretemp*") for the CPU
+    /// temperature. Auto-detected when unset.
+    pub cpu_sensor: Option<String>,
+    /// Disk usage and throughput groups, toggled with `d`.
+    pub show_disks: bool,
+    /// Mount points to show, all when empty.
+    pub disk_mounts: Vec<String>,
+    /// Filesystem types to show ("ext4", "btrfs"), all when empty.
+    pub disk_fs_types: Vec<String>,
+    /// /proc/meminfo breakdown (cache, zram, hugepages), toggled with `m`.
+    pub show_memory_details: bool,
+    /// Top processes panel, toggled with `p`.
+    pub show_processes: bool,
+    pub process_count: usize,
+    pub process_sort: ProcessSort,
+    /// Keyed by metric: cpu_load, cpu_temp, gpu_util, gpu_temp, gpu_mem, mem, swap.
+    pub thresholds: HashMap<String, ThresholdConfig>,
+    /// Threshold colors, the theme's warning and critical colors when unset.
+    pub warning_color: Option<String>,
+    pub critical_color: Option<String>,
+}
+
+impl Default for SysInfoConfig {
+    fn default() -> Self {
+        Self {
+            history_length: 150,
+            view: SysInfoView::Bars,
+            show_cores: false,
+            core_view: CoreView::Heatmap,
+            cpu_sensor: None,
+            show_disks: true,
+            disk_mounts: Vec::new(),
+            disk_fs_types: Vec::new(),
+            show_memory_details: false,
+            show_processes: false,
+            process_count: 8,
+            process_sort: ProcessSort::Cpu,
+            thresholds: default_thresholds(),
+            warning_color: None,
+            critical_color: None,
+        }
+    }
+}
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct NetworkConfig {
+    /// Interface names or globs ("wlan*", "enp3s0"), all but loopback when empty.
+    pub interfaces: Vec<String>,
+    pub show_addresses: bool,
+    /// Throughput samples kept per interface, one per poll (2s).
+    pub history_length: usize,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            interfaces: Vec::new(),
+            show_addresses: true,
+            history_length: 60,
+        }
+    }
+}
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct LoggingConfig {
+    /// Messages below this level are dropped.
+    pub level: LogLevel,
+    /// Log file path, `$XDG_STATE_HOME/gjpanel/gjpanel.log` when unset.
+    pub file: Option<String>,
+    /// The log is rotated once it grows past this size.
+    pub max_size_kb: u64,
+    /// Rotated files kept as `gjpanel.log.1` (newest) to `.N`.
+    pub max_files: usize,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: LogLevel::Info,
+            file: None,
+            max_size_kb: 1024,
+            max_files: 3,
+        }
+    }
+}
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ThemeConfig {
+    /// Built-in palette ("default", "catppuccin", "gruvbox", "nord"), a theme
+    /// file in ~/.config/gjpanel/themes (without .toml) or a path to one.
+    pub name: String,
+    /// Semantic colors overriding the palette, as names or hex values.
+    pub fg: Option<String>,
+    pub muted: Option<String>,
+    pub accent: Option<String>,
+    pub warning: Option<String>,
+    pub critical: Option<String>,
+    pub background: Option<String>,
+    pub border: Option<String>,
+}
+
+impl Default for ThemeConfig {
+    fn default() -> Self {
+        Self {
+            name: "default".to_string(),
+            fg: None,
+            muted: None,
+            accent: None,
+            warning: None,
+            critical: None,
+            background: None,
+            border: None,
+        }
+    }
+}
 #[derive(Debug, Deserialize)]
 pub struct Config {
     pub clock: ClockConfig,
     pub weather: WeatherConfig,
     pub workspaces: WorkspacesConfig,
+    #[serde(default)]
+    pub sysinfo: SysInfoConfig,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    #[serde(default)]
+    pub logging: LoggingConfig,
+    #[serde(default)]
+    pub theme: ThemeConfig,
 }
 
 pub fn load_config(path: &str) -> Config {
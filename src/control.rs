@@ -0,0 +1,123 @@
+use crate::logger::Logger;
+use crate::paths;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::Duration;
+
+const REPLY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A command line received on the control socket, answered through `reply`.
+pub struct ControlRequest {
+    pub args: Vec<String>,
+    pub reply: Sender<String>,
+}
+
+pub fn socket_path() -> PathBuf {
+    paths::runtime_dir().join("gjpanel.sock")
+}
+
+/// Receives control requests and removes the socket file when dropped.
+pub struct ControlServer {
+    pub requests: Receiver<ControlRequest>,
+    /// The socket file, when this panel bound it.
+    path: Option<PathBuf>,
+}
+
+impl Drop for ControlServer {
+    fn drop(&mut self) {
+        if let Some(path) = &self.path {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Listens on the control socket in a background thread. Each connection sends
+/// one whitespace separated command line and receives the reply until EOF.
+/// A socket another panel still answers on is left alone.
+pub fn spawn_server(logger: &'static Logger) -> ControlServer {
+    let (tx, rx) = mpsc::channel::<ControlRequest>();
+    let path = socket_path();
+    if UnixStream::connect(&path).is_ok() {
+        logger.warn(&format!(
+            "Another panel is listening on {}, control socket disabled",
+            path.display()
+        ));
+        return ControlServer {
+            requests: rx,
+            path: None,
+        };
+    }
+    // Nothing answers, so the file is left over from a panel that crashed
+    let _ = std::fs::remove_file(&path);
+
+    match UnixListener::bind(&path) {
+        Ok(listener) => {
+            std::thread::spawn(move || {
+                for stream in listener.incoming() {
+                    match stream {
+                        Ok(stream) => {
+                            if let Err(e) = handle_client(stream, &tx) {
+                                logger.error(&format!("Control socket client error: {}", e));
+                            }
+                        }
+                        Err(e) => logger.error(&format!("Control socket accept failed: {}", e)),
+                    }
+                }
+            });
+            ControlServer {
+                requests: rx,
+                path: Some(path),
+            }
+        }
+        Err(e) => {
+            logger.error(&format!(
+                "Failed to bind control socket {}: {}",
+                path.display(),
+                e
+            ));
+            ControlServer {
+                requests: rx,
+                path: None,
+            }
+        }
+    }
+}
+
+fn handle_client(stream: UnixStream, tx: &Sender<ControlRequest>) -> Result<(), String> {
+    let mut line = String::new();
+    BufReader::new(&stream)
+        .read_line(&mut line)
+        .map_err(|e| e.to_string())?;
+
+    let (reply_tx, reply_rx) = mpsc::channel::<String>();
+    tx.send(ControlRequest {
+        args: line.split_whitespace().map(str::to_string).collect(),
+        reply: reply_tx,
+    })
+    .map_err(|e| e.to_string())?;
+
+    let reply = reply_rx
+        .recv_timeout(REPLY_TIMEOUT)
+        .unwrap_or_else(|_| "error: panel did not respond".to_string());
+    (&stream)
+        .write_all(format!("{}\n", reply).as_bytes())
+        .map_err(|e| e.to_string())
+}
+
+/// Client side of the control socket, used by `rust-panel ctl ...`.
+pub fn send_command(args: &[String]) -> Result<String, String> {
+    let path = socket_path();
+    let mut stream = UnixStream::connect(&path)
+        .map_err(|e| format!("Failed to connect to {}: {}", path.display(), e))?;
+    stream
+        .write_all(format!("{}\n", args.join(" ")).as_bytes())
+        .map_err(|e| e.to_string())?;
+
+    let mut reply = String::new();
+    stream
+        .read_to_string(&mut reply)
+        .map_err(|e| e.to_string())?;
+    Ok(reply.trim_end().to_string())
+}
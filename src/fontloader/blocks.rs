@@ -0,0 +1,271 @@
+use super::render::Rendered;
+
+const GLYPH_HEIGHT: usize = 5;
+
+/// 3x5 pixel glyphs in the spirit of tty-clock, `#` for a set pixel.
+const GLYPHS: [(char, [&str; GLYPH_HEIGHT]); 46] = [
+    ('0', ["###", "#.#", "#.#", "#.#", "###"]),
+    ('1', [".#.", "##.", ".#.", ".#.", "###"]),
+    ('2', ["###", "..#", "###", "#..", "###"]),
+    ('3', ["###", "..#", "###", "..#", "###"]),
+    ('4', ["#.#", "#.#", "###", "..#", "..#"]),
+    ('5', ["###", "#..", "###", "..#", "###"]),
+    ('6', ["###", "#..", "###", "#.#", "###"]),
+    ('7', ["###", "..#", "..#", "..#", "..#"]),
+    ('8', ["###", "#.#", "###", "#.#", "###"]),
+    ('9', ["###", "#.#", "###", "..#", "###"]),
+    (':', [".", "#", ".", "#", "."]),
+    ('.', [".", ".", ".", ".", "#"]),
+    ('-', ["...", "...", "###", "...", "..."]),
+    ('+', ["...", ".#.", "###", ".#.", "..."]),
+    ('/', ["..#", "..#", ".#.", "#..", "#.."]),
+    ('%', ["#.#", "..#", ".#.", "#..", "#.#"]),
+    ('°', ["###", "#.#", "###", "...", "..."]),
+    (' ', ["..", "..", "..", "..", ".."]),
+    ('A', [".#.", "#.#", "###", "#.#", "#.#"]),
+    ('B', ["##.", "#.#", "##.", "#.#", "##."]),
+    ('C', [".##", "#..", "#..", "#..", ".##"]),
+    ('D', ["##.", "#.#", "#.#", "#.#", "##."]),
+    ('E', ["###", "#..", "##.", "#..", "###"]),
+    ('F', ["###", "#..", "##.", "#..", "#.."]),
+    ('G', [".##", "#..", "#.#", "#.#", ".##"]),
+    ('H', ["#.#", "#.#", "###", "#.#", "#.#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..#", "..#", "..#", "#.#", ".#."]),
+    ('K', ["#.#", "#.#", "##.", "#.#", "#.#"]),
+    ('L', ["#..", "#..", "#..", "#..", "###"]),
+    ('M', ["#.#", "###", "###", "#.#", "#.#"]),
+    ('N', ["##.", "#.#", "#.#", "#.#", "#.#"]),
+    ('O', [".#.", "#.#", "#.#", "#.#", ".#."]),
+    ('P', ["##.", "#.#", "##.", "#..", "#.."]),
+    ('Q', [".#.", "#.#", "#.#", "##.", ".##"]),
+    ('R', ["##.", "#.#", "##.", "#.#", "#.#"]),
+    ('S', [".##", "#..", ".#.", "..#", "##."]),
+    ('T', ["###", ".#.", ".#.", ".#.", ".#."]),
+    ('U', ["#.#", "#.#", "#.#", "#.#", "###"]),
+    ('V', ["#.#", "#.#", "#.#", ".#.", ".#."]),
+    ('W', ["#.#", "#.#", "###", "###", "#.#"]),
+    ('X', ["#.#", "#.#", ".#.", "#.#", "#.#"]),
+    ('Y', ["#.#", "#.#", ".#.", ".#.", ".#."]),
+    ('Z', ["###", "..#", ".#.", "#..", "###"]),
+    (',', [".", ".", ".", "#", "#"]),
+    ('!', ["#", "#", "#", ".", "#"]),
+];
+
+/// How pixels are packed into terminal cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockStyle {
+    /// 1x2 pixels per cell with ▀ ▄ █.
+    HalfBlocks,
+    /// 2x3 pixels per cell from the Symbols for Legacy Computing block.
+    Sextants,
+    /// 2x4 pixels per cell as braille dots.
+    Braille,
+}
+
+impl BlockStyle {
+    fn cell_size(self) -> (usize, usize) {
+        match self {
+            BlockStyle::HalfBlocks => (1, 2),
+            BlockStyle::Sextants => (2, 3),
+            BlockStyle::Braille => (2, 4),
+        }
+    }
+
+    /// The character for a cell, `pixel(x, y)` telling whether a pixel is set.
+    fn cell(self, pixel: impl Fn(usize, usize) -> bool) -> char {
+        match self {
+            BlockStyle::HalfBlocks => match (pixel(0, 0), pixel(0, 1)) {
+                (false, false) => ' ',
+                (true, false) => '▀',
+                (false, true) => '▄',
+                (true, true) => '█',
+            },
+            BlockStyle::Sextants => {
+                let bits = (0..6).fold(0u32, |bits, i| bits | ((pixel(i % 2, i / 2) as u32) << i));
+                match bits {
+                    0 => ' ',
+                    21 => '▌',
+                    42 => '▐',
+                    63 => '█',
+                    // U+1FB00 onwards skips the patterns that already exist as half blocks.
+                    _ => {
+                        char::from_u32(0x1FB00 + bits - 1 - (bits > 21) as u32 - (bits > 42) as u32)
+                            .unwrap_or('?')
+                    }
+                }
+            }
+            BlockStyle::Braille => {
+                const DOTS: [(usize, usize, u32); 8] = [
+                    (0, 0, 0x01),
+                    (0, 1, 0x02),
+                    (0, 2, 0x04),
+                    (1, 0, 0x08),
+                    (1, 1, 0x10),
+                    (1, 2, 0x20),
+                    (0, 3, 0x40),
+                    (1, 3, 0x80),
+                ];
+                let bits = DOTS
+                    .iter()
+                    .filter(|(x, y, _)| pixel(*x, *y))
+                    .fold(0, |bits, (_, _, bit)| bits | bit);
+                char::from_u32(0x2800 + bits).unwrap_or(' ')
+            }
+        }
+    }
+}
+
+/// Big text drawn from a built-in pixel font, selected with `blocks:2`,
+/// `sextants:3` or `braille:2` where the number is the pixel scale.
+#[derive(Debug, Clone, Copy)]
+pub struct BlockFont {
+    style: BlockStyle,
+    scale: usize,
+}
+
+impl BlockFont {
+    pub fn parse(spec: &str) -> Option<Self> {
+        let (name, scale) = match spec.split_once(':') {
+            Some((name, scale)) => (name, scale.parse::<usize>().ok()?),
+            None => (spec, 1),
+        };
+        let style = match name {
+            "blocks" => BlockStyle::HalfBlocks,
+            "sextants" => BlockStyle::Sextants,
+            "braille" => BlockStyle::Braille,
+            _ => return None,
+        };
+        Some(Self {
+            style,
+            scale: scale.clamp(1, 8),
+        })
+    }
+
+    pub fn render(&self, text: &str) -> Rendered {
+        let scale = self.scale;
+        let mut pixels: Vec<Vec<bool>> = vec![Vec::new(); GLYPH_HEIGHT * scale];
+        let mut pixel_owners: Vec<usize> = Vec::new();
+        for (index, c) in text.chars().enumerate() {
+            let Some((_, glyph)) = GLYPHS.iter().find(|(g, _)| *g == c.to_ascii_uppercase()) else {
+                continue;
+            };
+            if !pixel_owners.is_empty() {
+                for row in &mut pixels {
+                    row.extend(std::iter::repeat_n(false, scale));
+                }
+                pixel_owners.extend(std::iter::repeat_n(index, scale));
+            }
+            for (y, row) in pixels.iter_mut().enumerate() {
+                for set in glyph[y / scale].chars().map(|p| p == '#') {
+                    row.extend(std::iter::repeat_n(set, scale));
+                }
+            }
+            pixel_owners.extend(std::iter::repeat_n(index, glyph[0].len() * scale));
+        }
+
+        let (cell_width, cell_height) = self.style.cell_size();
+        let width = pixel_owners.len().div_ceil(cell_width);
+        let height = pixels.len().div_ceil(cell_height);
+        let pixel = |x: usize, y: usize| {
+            pixels
+                .get(y)
+                .and_then(|row| row.get(x))
+                .copied()
+                .unwrap_or(false)
+        };
+        let rows = (0..height)
+            .map(|row| {
+                (0..width)
+                    .map(|column| {
+                        self.style
+                            .cell(|x, y| pixel(column * cell_width + x, row * cell_height + y))
+                    })
+                    .collect()
+            })
+            .collect();
+        let owners = (0..width)
+            .map(|column| pixel_owners[column * cell_width])
+            .collect();
+        Rendered { rows, owners }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(rendered: &Rendered) -> Vec<String> {
+        rendered
+            .rows
+            .iter()
+            .map(|row| row.iter().collect())
+            .collect()
+    }
+
+    #[test]
+    fn parses_specs() {
+        let font = BlockFont::parse("blocks:2").unwrap();
+        assert_eq!((font.style, font.scale), (BlockStyle::HalfBlocks, 2));
+        let font = BlockFont::parse("braille").unwrap();
+        assert_eq!((font.style, font.scale), (BlockStyle::Braille, 1));
+        assert_eq!(BlockFont::parse("sextants:0").unwrap().scale, 1);
+        assert_eq!(BlockFont::parse("sextants:99").unwrap().scale, 8);
+        assert!(BlockFont::parse("blocks:x").is_none());
+        assert!(BlockFont::parse("blocks:-1").is_none());
+        assert!(BlockFont::parse("small").is_none());
+    }
+
+    #[test]
+    fn half_blocks() {
+        let one = BlockFont::parse("blocks").unwrap().render("1");
+        assert_eq!(rows(&one), vec!["▄█ ", " █ ", "▀▀▀"]);
+
+        let two = BlockFont::parse("blocks:2").unwrap().render("1");
+        assert_eq!(
+            rows(&two),
+            vec!["  ██  ", "████  ", "  ██  ", "  ██  ", "██████"]
+        );
+    }
+
+    #[test]
+    fn sextant_cells() {
+        let style = BlockStyle::Sextants;
+        assert_eq!(style.cell(|_, _| false), ' ');
+        assert_eq!(style.cell(|_, _| true), '█');
+        assert_eq!(style.cell(|x, _| x == 0), '▌');
+        assert_eq!(style.cell(|x, _| x == 1), '▐');
+        assert_eq!(style.cell(|x, y| x == 0 && y == 0), '\u{1FB00}');
+        assert_eq!(style.cell(|x, y| x == 0 && y == 1), '\u{1FB03}');
+        // Sextant-235 and 23456 sit after the skipped half block patterns.
+        assert_eq!(
+            style.cell(|x, y| matches!((x, y), (1, 0) | (0, 1) | (0, 2))),
+            '\u{1FB14}'
+        );
+        assert_eq!(style.cell(|x, y| (x, y) != (0, 0)), '\u{1FB3B}');
+
+        let colon = BlockFont::parse("sextants").unwrap().render(":");
+        assert_eq!(rows(&colon), vec!["\u{1FB03}", "\u{1FB00}"]);
+    }
+
+    #[test]
+    fn braille_cells() {
+        let style = BlockStyle::Braille;
+        assert_eq!(style.cell(|_, _| false), '\u{2800}');
+        assert_eq!(style.cell(|_, _| true), '⣿');
+        assert_eq!(style.cell(|x, _| x == 0), '⡇');
+        assert_eq!(style.cell(|_, y| y == 3), '⣀');
+
+        let one = BlockFont::parse("braille:2").unwrap().render("1");
+        assert_eq!(rows(&one), vec!["⣤⣿\u{2800}", "\u{2800}⣿\u{2800}", "⠛⠛⠛"]);
+    }
+
+    #[test]
+    fn columns_belong_to_their_character() {
+        let rendered = BlockFont::parse("blocks").unwrap().render("1:1");
+        assert_eq!(rendered.width(), 3 + 1 + 1 + 1 + 3);
+        assert_eq!(rendered.owners, vec![0, 0, 0, 1, 1, 2, 2, 2, 2]);
+        // Unknown characters are skipped.
+        assert_eq!(BlockFont::parse("blocks").unwrap().render("~1").width(), 3);
+    }
+}
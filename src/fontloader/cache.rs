@@ -0,0 +1,105 @@
+use crate::logger::Logger;
+use figlet_rs::FIGfont;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::rc::{Rc, Weak};
+use std::time::{Duration, Instant, SystemTime};
+
+use super::search::{self, FontSource};
+
+/// How often a handle asks the cache whether its font changed on disk.
+const CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+struct Entry {
+    font: Weak<FIGfont>,
+    modified: Option<SystemTime>,
+}
+
+thread_local! {
+    /// Loaded fonts keyed by resolved path and control files. Entries are weak
+    /// so a font is freed once no widget holds a handle to it.
+    static CACHE: RefCell<HashMap<String, Entry>> = RefCell::new(HashMap::new());
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+/// Cache key and newest modification time for a `name[+control...]` spec.
+fn identify(spec: &str) -> (String, Option<SystemTime>) {
+    let mut parts = spec.split('+');
+    let name = parts.next().unwrap_or_default();
+    let (mut key, mut newest) = match search::resolve(name) {
+        Some(FontSource::File(path)) => (path.display().to_string(), modified(&path)),
+        Some(FontSource::Embedded(_)) => (format!("embedded:{}", name), None),
+        None => (format!("missing:{}", name), None),
+    };
+    for control in parts {
+        match search::resolve_control(control) {
+            Some(path) => {
+                key.push_str(&format!("+{}", path.display()));
+                newest = newest.max(modified(&path));
+            }
+            None => key.push_str(&format!("+missing:{}", control)),
+        }
+    }
+    (key, newest)
+}
+
+/// Returns the shared font for `spec`, loading it if no widget holds it yet or
+/// the file changed since it was loaded.
+fn get(spec: &str, logger: &Logger) -> Rc<FIGfont> {
+    let (key, modified) = identify(spec);
+    CACHE.with_borrow_mut(|cache| {
+        if let Some(entry) = cache.get(&key)
+            && let Some(font) = entry.font.upgrade()
+        {
+            if entry.modified == modified {
+                return font;
+            }
+            logger.info(&format!("Font {} changed on disk, reloading", key));
+        }
+
+        let font = Rc::new(super::load_uncached(spec, logger));
+        cache.retain(|_, entry| entry.font.strong_count() > 0);
+        cache.insert(
+            key,
+            Entry {
+                font: Rc::downgrade(&font),
+                modified,
+            },
+        );
+        font
+    })
+}
+
+/// A widget's reference to a cached font, swapped for the reloaded font when
+/// the file changes.
+pub struct FontHandle {
+    spec: String,
+    font: RefCell<Rc<FIGfont>>,
+    checked: Cell<Instant>,
+    logger: &'static Logger,
+}
+
+impl FontHandle {
+    pub fn new(spec: &str, logger: &'static Logger) -> Self {
+        Self {
+            spec: spec.to_string(),
+            font: RefCell::new(get(spec, logger)),
+            checked: Cell::new(Instant::now()),
+            logger,
+        }
+    }
+
+    pub fn get(&self) -> Rc<FIGfont> {
+        if self.checked.get().elapsed() >= CHECK_INTERVAL {
+            self.checked.set(Instant::now());
+            let font = get(&self.spec, self.logger);
+            *self.font.borrow_mut() = font;
+        }
+        self.font.borrow().clone()
+    }
+}
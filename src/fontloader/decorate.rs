@@ -0,0 +1,141 @@
+use crate::color::{Gradient, parse_color};
+use crate::config::{GradientDirection, TextEffect};
+use crate::theme;
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span, Text},
+};
+
+use super::render::Rendered;
+
+const OUTLINE_CHAR: char = '░';
+
+/// Coloring applied to big text on top of the widget's base style.
+#[derive(Debug, Clone)]
+pub struct TextDecoration {
+    gradient: Gradient,
+    direction: GradientDirection,
+    /// Colors cycled per input character, taking precedence over the gradient.
+    colors: Vec<Color>,
+    effect: TextEffect,
+}
+
+impl Default for TextDecoration {
+    fn default() -> Self {
+        Self {
+            gradient: Gradient::new(Vec::new()),
+            direction: GradientDirection::Vertical,
+            colors: Vec::new(),
+            effect: TextEffect::None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Cell {
+    Blank,
+    Glyph(char, Color),
+    Effect(char),
+}
+
+impl TextDecoration {
+    pub fn new(
+        gradient: &[String],
+        direction: GradientDirection,
+        colors: &[String],
+        effect: TextEffect,
+    ) -> Self {
+        Self {
+            gradient: Gradient::from_strings(gradient),
+            direction,
+            colors: colors.iter().filter_map(|c| parse_color(c)).collect(),
+            effect,
+        }
+    }
+
+    /// The same colors without shadow or outline, for plain text fallbacks.
+    pub fn without_effect(&self) -> Self {
+        Self {
+            effect: TextEffect::None,
+            ..self.clone()
+        }
+    }
+
+    /// Columns and rows the effect adds around the text.
+    pub fn extra_size(&self) -> (usize, usize) {
+        match self.effect {
+            TextEffect::None => (0, 0),
+            TextEffect::Shadow => (1, 1),
+            TextEffect::Outline => (2, 2),
+        }
+    }
+
+    fn color(&self, rendered: &Rendered, row: usize, column: usize, base: Color) -> Color {
+        if !self.colors.is_empty() {
+            return self.colors[rendered.owners[column] % self.colors.len()];
+        }
+        if self.gradient.is_empty() {
+            return base;
+        }
+        let position =
+            |index: usize, len: usize| index as f32 / len.saturating_sub(1).max(1) as f32;
+        match self.direction {
+            GradientDirection::Vertical => self.gradient.at(position(row, rendered.rows.len())),
+            GradientDirection::Horizontal => self.gradient.at(position(column, rendered.width())),
+        }
+    }
+
+    /// Styles the rendered grid, adding the shadow or outline around glyphs.
+    pub fn apply(&self, rendered: &Rendered, style: Style) -> Text<'static> {
+        let base = style.fg.unwrap_or(Color::Reset);
+        let (extra_columns, extra_rows) = self.extra_size();
+        let offset = if self.effect == TextEffect::Outline {
+            1
+        } else {
+            0
+        };
+        let width = rendered.width() + extra_columns;
+        let mut grid = vec![vec![Cell::Blank; width]; rendered.rows.len() + extra_rows];
+
+        let glyphs = rendered.rows.iter().enumerate().flat_map(|(row, chars)| {
+            chars
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| **c != ' ')
+                .map(move |(column, c)| (row, column, *c))
+        });
+        for (row, column, c) in glyphs.clone() {
+            match self.effect {
+                TextEffect::None => {}
+                TextEffect::Shadow => grid[row + 1][column + 1] = Cell::Effect(c),
+                TextEffect::Outline => {
+                    for cells in &mut grid[row..=row + 2] {
+                        cells[column..=column + 2].fill(Cell::Effect(OUTLINE_CHAR));
+                    }
+                }
+            }
+        }
+        for (row, column, c) in glyphs {
+            let color = self.color(rendered, row, column, base);
+            grid[row + offset][column + offset] = Cell::Glyph(c, color);
+        }
+
+        // The theme's border color is dim enough to sit behind any glyph color.
+        let effect_style = style.fg(theme::current().border);
+        let lines: Vec<Line> = grid
+            .into_iter()
+            .map(|row| {
+                Line::from(
+                    row.into_iter()
+                        .map(|cell| match cell {
+                            Cell::Blank => Span::styled(" ", style),
+                            Cell::Glyph(c, color) => Span::styled(c.to_string(), style.fg(color)),
+                            Cell::Effect(c) => Span::styled(c.to_string(), effect_style),
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+        Text::from(lines)
+    }
+}
@@ -1,61 +1,173 @@
-use figlet_rs::{FIGfont, FIGure};
-use ratatui::{
-    style::Style,
-    text::{Line, Text},
-};
-use std::fs;
-
-pub fn load_font_from_file_or_err(path: &str) -> FIGfont {
-    match fs::read_to_string(path) {
-        Ok(content) => match FIGfont::from_content(&content) {
-            Ok(font) => font,
-            Err(e) => {
-                eprintln!("Invalid font format in {}: {}", path, e);
-                make_error_font()
-            }
-        },
+use crate::logger::Logger;
+use figlet_rs::FIGfont;
+use ratatui::{style::Style, text::Text};
+use std::path::Path;
+
+mod blocks;
+mod cache;
+mod decorate;
+mod parse;
+mod render;
+mod search;
+
+use blocks::BlockFont;
+pub use cache::FontHandle;
+pub use decorate::TextDecoration;
+use render::Rendered;
+use search::FontSource;
+pub use search::list_fonts;
+
+fn load_font_file(path: &Path, logger: &Logger) -> FIGfont {
+    match parse::read_font_file(path).and_then(|content| parse::parse_font(&content)) {
+        Ok(font) => font,
         Err(e) => {
-            eprintln!("Failed to read font file {}: {}", path, e);
+            logger.error(&format!("Invalid font {}: {}", path.display(), e));
             make_error_font()
         }
     }
 }
 
-pub fn load_font_by_name_or_err(font_name: &str) -> Option<FIGfont> {
-    if font_name.is_empty() {
-        return None;
+/// Parses a font spec of the form `name[+control...]`, e.g. "future+utf8",
+/// where each control is a FIGlet `.flc` file applied in order. Widgets go
+/// through `load_font` so that fonts are shared.
+fn load_uncached(font_spec: &str, logger: &Logger) -> FIGfont {
+    let mut parts = font_spec.split('+');
+    let font_name = parts.next().unwrap_or_default();
+    let mut font = match search::resolve(font_name) {
+        Some(FontSource::File(path)) => load_font_file(&path, logger),
+        Some(FontSource::Embedded(content)) => parse::parse_font(content).unwrap_or_else(|e| {
+            logger.error(&format!("Invalid embedded font {}: {}", font_name, e));
+            make_error_font()
+        }),
+        None => {
+            logger.error(&format!(
+                "Font '{}' not found in {:?}",
+                font_name,
+                search::search_dirs()
+            ));
+            make_error_font()
+        }
+    };
+
+    for control in parts {
+        let stages = search::resolve_control(control)
+            .ok_or_else(|| format!("Control file '{}' not found", control))
+            .and_then(|path| parse::read_font_file(&path))
+            .and_then(|content| parse::parse_control_file(&content));
+        match stages {
+            Ok(stages) => parse::apply_control_stages(&mut font, &stages),
+            Err(e) => logger.error(&e),
+        }
     }
-    let default_path = format!("./fonts/{}.flf", font_name);
-    Some(load_font_from_file_or_err(&default_path))
+    font
 }
 
-pub fn render_figlet_text<'a>(font: &'a FIGfont, text: &'a str) -> FIGure<'a> {
-    font.convert(text).unwrap_or_else(|| {
-        font.convert("ERR")
-            .unwrap_or_else(|| font.convert("!").unwrap())
-    })
+/// One entry of a font chain: a FIGlet font or the built-in block renderer.
+enum ChainFont {
+    Figlet(FontHandle),
+    Blocks(BlockFont),
 }
 
-pub fn to_styled_text<'a>(font: &'a Option<FIGfont>, text: &'a str, style: Style) -> Text<'a> {
-    let mut result_text = Text::default();
-
-    match font {
-        Some(font) => {
-            let fig_text = render_figlet_text(font, text);
-            for line in fig_text.to_string().lines() {
-                result_text
-                    .lines
-                    .push(Line::styled(line.to_string(), style));
-            }
+impl ChainFont {
+    fn new(spec: &str, logger: &'static Logger) -> Self {
+        match BlockFont::parse(spec) {
+            Some(blocks) => ChainFont::Blocks(blocks),
+            None => ChainFont::Figlet(FontHandle::new(spec, logger)),
         }
-        None => {
-            result_text.lines.push(Line::styled(text, style));
+    }
+
+    fn render(&self, text: &str) -> Rendered {
+        match self {
+            ChainFont::Figlet(handle) => render::render(&handle.get(), text),
+            ChainFont::Blocks(blocks) => blocks.render(text),
         }
     }
+}
+
+/// Fonts tried in order until one fits the available width, falling back to
+/// plain text when none does.
+pub struct FontChain {
+    fonts: Vec<ChainFont>,
+}
+
+impl FontChain {
+    /// The rendering from the first font that fits in `width` columns.
+    pub fn render(&self, text: &str, width: usize) -> Option<Rendered> {
+        self.fonts.iter().find_map(|font| {
+            let rendered = font.render(text);
+            (rendered.width() <= width && !rendered.is_blank()).then_some(rendered)
+        })
+    }
+}
+
+/// Loads a comma separated fallback chain such as "large,small" or
+/// "blocks:2,blocks:1", `None` for plain text.
+pub fn load_font(font_spec: &str, logger: &'static Logger) -> Option<FontChain> {
+    let fonts: Vec<ChainFont> = font_spec
+        .split(',')
+        .map(str::trim)
+        .filter(|spec| !spec.is_empty())
+        .map(|spec| ChainFont::new(spec, logger))
+        .collect();
+    if fonts.is_empty() {
+        return None;
+    }
+    Some(FontChain { fonts })
+}
 
-    result_text
+/// Renders `text` with the first font of the chain that fits in `width`, or as
+/// a plain line.
+pub fn to_styled_text(
+    font: &Option<FontChain>,
+    text: &str,
+    style: Style,
+    width: u16,
+) -> Text<'static> {
+    to_decorated_text(font, text, style, width, &TextDecoration::default())
+}
+
+/// Like `to_styled_text`, with gradient, per-character colors or effects.
+pub fn to_decorated_text(
+    font: &Option<FontChain>,
+    text: &str,
+    style: Style,
+    width: u16,
+    decoration: &TextDecoration,
+) -> Text<'static> {
+    let available = (width as usize).saturating_sub(decoration.extra_size().0);
+    match font
+        .as_ref()
+        .and_then(|chain| chain.render(text, available))
+    {
+        Some(rendered) => decoration.apply(&rendered, style),
+        None => decoration
+            .without_effect()
+            .apply(&Rendered::plain(text), style),
+    }
 }
 
 fn make_error_font() -> FIGfont {
     FIGfont::standard().unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logger::test_logger;
+
+    #[test]
+    fn chain_falls_back_to_a_font_that_fits() {
+        let chain = load_font("blocks:2, blocks:1", test_logger()).unwrap();
+        // Two 3 pixel glyphs and a gap: 14 columns at scale 2, 7 at scale 1.
+        assert_eq!(chain.render("12", 20).unwrap().width(), 14);
+        assert_eq!(chain.render("12", 10).unwrap().width(), 7);
+        assert!(chain.render("12", 6).is_none());
+        // Text the fonts can't draw is blank and skipped as well.
+        assert!(chain.render("~", 20).is_none());
+
+        let text = to_styled_text(&Some(chain), "12", Style::default(), 6);
+        assert_eq!(text.lines.len(), 1);
+        assert_eq!(text.lines[0].to_string(), "12");
+        assert!(load_font(" , ", test_logger()).is_none());
+    }
+}
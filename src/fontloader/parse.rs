@@ -0,0 +1,362 @@
+use figlet_rs::{FIGcharacter, FIGfont, HeaderLine};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Cursor, Read};
+use std::path::Path;
+
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+const GERMAN_CODES: [u32; 7] = [196, 214, 220, 228, 246, 252, 223];
+
+/// Reads a font or control file, unpacking the first entry of zip archives
+/// as figlet distributes its compressed fonts that way.
+pub fn read_font_file(path: &Path) -> Result<String, String> {
+    let bytes = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    if !bytes.starts_with(ZIP_MAGIC) {
+        return String::from_utf8(bytes)
+            .map_err(|e| format!("{} is not UTF-8: {}", path.display(), e));
+    }
+
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))
+        .map_err(|e| format!("Invalid zip archive {}: {}", path.display(), e))?;
+    let mut entry = archive
+        .by_index(0)
+        .map_err(|e| format!("Empty zip archive {}: {}", path.display(), e))?;
+    let mut content = String::new();
+    entry
+        .read_to_string(&mut content)
+        .map_err(|e| format!("Failed to unpack {}: {}", path.display(), e))?;
+    Ok(content)
+}
+
+/// Parses the header line by characters rather than bytes, since TOIlet
+/// fonts may use a multibyte hardblank.
+fn parse_header(line: &str) -> Result<HeaderLine, String> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let first = fields.first().ok_or("Empty font header")?;
+    let signature: String = first.chars().take(5).collect();
+    if signature != "flf2a" && signature != "tlf2a" {
+        return Err(format!("Unknown font signature '{}'", signature));
+    }
+    let hardblank = first.chars().nth(5).ok_or("Font header has no hardblank")?;
+    let number = |index: usize| {
+        fields
+            .get(index)
+            .and_then(|field| field.parse::<i32>().ok())
+    };
+    let required = |index: usize, name: &str| {
+        number(index).ok_or_else(|| format!("Font header is missing {}", name))
+    };
+
+    Ok(HeaderLine {
+        header_line: line.to_string(),
+        signature,
+        hardblank,
+        height: required(1, "height")?,
+        baseline: required(2, "baseline")?,
+        max_length: required(3, "max length")?,
+        old_layout: required(4, "old layout")?,
+        comment_lines: required(5, "comment lines")?,
+        print_direction: number(6),
+        full_layout: number(7),
+        codetag_count: number(8),
+    })
+}
+
+/// Character code in decimal, `0x` hex or leading-zero octal, possibly negative.
+fn parse_code(text: &str) -> Option<i64> {
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    let value = if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        i64::from_str_radix(hex, 16).ok()?
+    } else if text.len() > 1 && text.starts_with('0') {
+        i64::from_str_radix(&text[1..], 8).ok()?
+    } else {
+        text.parse().ok()?
+    };
+    Some(if negative { -value } else { value })
+}
+
+/// One glyph row with its endmarks removed. The endmark is whatever the row
+/// ends with and may repeat on the glyph's last row. Hardblanks are kept for
+/// the smushing rules and replaced when rendering.
+fn glyph_row(line: &str) -> String {
+    let line = line.trim_end_matches(['\r', ' ']);
+    let row = match line.chars().last() {
+        Some(endmark) => line.trim_end_matches(endmark),
+        None => line,
+    };
+    row.to_string()
+}
+
+fn read_glyph(
+    lines: &[&str],
+    start: usize,
+    code: u32,
+    header: &HeaderLine,
+) -> Option<FIGcharacter> {
+    let height = header.height as usize;
+    let rows: Vec<String> = lines
+        .get(start..start + height)?
+        .iter()
+        .map(|line| glyph_row(line))
+        .collect();
+    let width = rows
+        .iter()
+        .map(|row| row.chars().count())
+        .max()
+        .unwrap_or(0) as u32;
+    Some(FIGcharacter {
+        code,
+        characters: rows,
+        width,
+        height: height as u32,
+    })
+}
+
+/// Parses FIGlet `.flf` and TOIlet `.tlf` fonts, including code-tagged glyphs.
+pub fn parse_font(content: &str) -> Result<FIGfont, String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let header = parse_header(lines.first().ok_or("Empty font file")?)?;
+    if header.height <= 0 {
+        return Err(format!("Invalid font height {}", header.height));
+    }
+    let height = header.height as usize;
+    let comment_lines = header.comment_lines.max(0) as usize;
+    let comments = lines
+        .get(1..1 + comment_lines)
+        .ok_or("Font file ends inside its comments")?
+        .join("\n");
+
+    let mut fonts = HashMap::new();
+    let mut index = 1 + comment_lines;
+    for code in (32..=126).chain(GERMAN_CODES) {
+        let Some(glyph) = read_glyph(&lines, index, code, &header) else {
+            break;
+        };
+        fonts.insert(code, glyph);
+        index += height;
+    }
+
+    while index < lines.len() {
+        let tag = lines[index].split_whitespace().next().and_then(parse_code);
+        match tag {
+            Some(code) => {
+                let glyph = read_glyph(&lines, index + 1, 0, &header)
+                    .ok_or_else(|| format!("Truncated glyph for code {}", code))?;
+                // Negative codes are figlet's "do not map" glyphs.
+                if let Ok(code) = u32::try_from(code) {
+                    fonts.insert(code, FIGcharacter { code, ..glyph });
+                }
+                index += height + 1;
+            }
+            None if lines[index].trim().is_empty() => index += 1,
+            None => return Err(format!("Invalid code tag '{}'", lines[index])),
+        }
+    }
+
+    Ok(FIGfont {
+        header_line: header,
+        comments,
+        fonts,
+    })
+}
+
+/// A character in a control file: literal, `\` escape, or `\` followed by a code.
+fn parse_control_char(text: &str) -> Option<u32> {
+    let mut chars = text.chars();
+    match (chars.next()?, chars.as_str()) {
+        ('\\', "") => Some('\\' as u32),
+        ('\\', "a") => Some(7),
+        ('\\', "b") => Some(8),
+        ('\\', "e") => Some(27),
+        ('\\', "f") => Some(12),
+        ('\\', "n") => Some(10),
+        ('\\', "r") => Some(13),
+        ('\\', "t") => Some(9),
+        ('\\', "v") => Some(11),
+        ('\\', "\\") => Some('\\' as u32),
+        ('\\', " ") => Some(' ' as u32),
+        ('\\', rest) => parse_code(rest).and_then(|code| u32::try_from(code).ok()),
+        (c, "") => Some(c as u32),
+        _ => None,
+    }
+}
+
+/// `a-z` style ranges or single characters from a `t` command.
+fn parse_control_range(text: &str) -> Option<(u32, u32)> {
+    if let Some(single) = parse_control_char(text) {
+        return Some((single, single));
+    }
+    let (from, to) = text.split_once('-')?;
+    Some((parse_control_char(from)?, parse_control_char(to)?))
+}
+
+/// Character mappings from a FIGlet `.flc` control file, one stage per `f`
+/// (freeze) command. Encoding commands (`g`, `h`, `j`, `b`, `u`) are ignored
+/// because panel text is already Unicode.
+pub fn parse_control_file(content: &str) -> Result<Vec<HashMap<u32, u32>>, String> {
+    let mut stages = vec![HashMap::new()];
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with("flc2a") {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let stage = stages.last_mut().unwrap();
+        match fields.as_slice() {
+            ["t", from, to, ..] => {
+                let (from_start, from_end) = parse_control_range(from)
+                    .ok_or_else(|| format!("Invalid control line '{}'", line))?;
+                let (to_start, _) = parse_control_range(to)
+                    .ok_or_else(|| format!("Invalid control line '{}'", line))?;
+                for (offset, code) in (from_start..=from_end).enumerate() {
+                    stage.insert(code, to_start + offset as u32);
+                }
+            }
+            ["f", ..] => stages.push(HashMap::new()),
+            [command, ..] if ["g", "h", "j", "b", "u"].contains(command) => {}
+            [from, to, ..] => {
+                let (Some(from), Some(to)) = (parse_code(from), parse_code(to)) else {
+                    return Err(format!("Invalid control line '{}'", line));
+                };
+                if let (Ok(from), Ok(to)) = (u32::try_from(from), u32::try_from(to)) {
+                    stage.insert(from, to);
+                }
+            }
+            _ => return Err(format!("Invalid control line '{}'", line)),
+        }
+    }
+    Ok(stages)
+}
+
+/// Bakes control file mappings into the font, so that mapped input characters
+/// render with the glyph of the character they translate to.
+pub fn apply_control_stages(font: &mut FIGfont, stages: &[HashMap<u32, u32>]) {
+    let mut inputs: Vec<u32> = stages
+        .iter()
+        .flat_map(|stage| stage.keys().copied())
+        .collect();
+    inputs.sort_unstable();
+    inputs.dedup();
+
+    let mut mapped = Vec::new();
+    for input in inputs {
+        let output = stages.iter().fold(input, |code, stage| {
+            stage.get(&code).copied().unwrap_or(code)
+        });
+        if output == input {
+            continue;
+        }
+        match font.fonts.get(&output) {
+            Some(glyph) => mapped.push(FIGcharacter {
+                code: input,
+                characters: glyph.characters.clone(),
+                width: glyph.width,
+                height: glyph.height,
+            }),
+            None => {
+                font.fonts.remove(&input);
+            }
+        }
+    }
+    for glyph in mapped {
+        font.fonts.insert(glyph.code, glyph);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    /// A one row high font whose glyph for `c` is `[c]`, followed by `tagged`
+    /// code-tagged glyphs.
+    fn font_source(signature: &str, tagged: &str) -> String {
+        let mut source = format!("{}$ 1 1 8 0 1\ncomment\n", signature);
+        for code in (32..=126).chain(GERMAN_CODES) {
+            let c = char::from_u32(code).unwrap();
+            source.push_str(&format!("[{}]@\n", c));
+        }
+        source.push_str(tagged);
+        source
+    }
+
+    fn glyph(font: &FIGfont, code: u32) -> &str {
+        &font.fonts[&code].characters[0]
+    }
+
+    #[test]
+    fn parses_required_and_german_glyphs() {
+        let font = parse_font(&font_source("flf2a", "")).unwrap();
+        assert_eq!(font.header_line.hardblank, '$');
+        assert_eq!(font.comments, "comment");
+        assert_eq!(glyph(&font, 'A' as u32), "[A]");
+        assert_eq!(glyph(&font, '@' as u32), "[@]");
+        assert_eq!(glyph(&font, 'ß' as u32), "[ß]");
+        assert_eq!(font.fonts[&('A' as u32)].width, 3);
+    }
+
+    #[test]
+    fn parses_tlf_code_tags() {
+        let tagged = "0x263A  WHITE SMILING FACE\n☺☺@@\n9731\n☃@\n0101\nOCT@\n-2\nskip@\n";
+        let font = parse_font(&font_source("tlf2a", tagged)).unwrap();
+        assert_eq!(glyph(&font, 0x263A), "☺☺");
+        assert_eq!(font.fonts[&0x263A].width, 2);
+        assert_eq!(glyph(&font, 9731), "☃");
+        assert_eq!(glyph(&font, 0o101), "OCT");
+        assert_eq!(font.fonts.len(), 95 + 7 + 2);
+    }
+
+    #[test]
+    fn rejects_bad_fonts() {
+        assert!(parse_font("xyz2a$ 1 1 8 0 0").is_err());
+        assert!(parse_font("flf2a$ 1 1 8 0 3\nonly one comment").is_err());
+        assert!(parse_font(&font_source("flf2a", "not-a-code\nx@\n")).is_err());
+    }
+
+    #[test]
+    fn parses_control_file_stages() {
+        let stages =
+            parse_control_file("flc2a\n# lower to upper\nt a-c x-z\n0x41 0x42\nu\nf\nt x X\n")
+                .unwrap();
+        assert_eq!(stages.len(), 2);
+        assert_eq!(stages[0][&('a' as u32)], 'x' as u32);
+        assert_eq!(stages[0][&('c' as u32)], 'z' as u32);
+        assert_eq!(stages[0][&0x41], 0x42);
+        assert_eq!(stages[1][&('x' as u32)], 'X' as u32);
+        assert!(parse_control_file("t").is_err());
+
+        let mut font = parse_font(&font_source("flf2a", "")).unwrap();
+        apply_control_stages(&mut font, &stages);
+        assert_eq!(glyph(&font, 'a' as u32), "[X]");
+        assert_eq!(glyph(&font, 'b' as u32), "[y]");
+    }
+
+    #[test]
+    fn reads_plain_and_zipped_files() {
+        let dir = std::env::temp_dir().join(format!("gjpanel-fonts-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let source = font_source("flf2a", "");
+
+        let plain = dir.join("plain.flf");
+        fs::write(&plain, &source).unwrap();
+        assert_eq!(read_font_file(&plain).unwrap(), source);
+
+        let zipped = dir.join("zipped.flf");
+        let mut writer = zip::ZipWriter::new(fs::File::create(&zipped).unwrap());
+        writer
+            .start_file("zipped.flf", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(source.as_bytes()).unwrap();
+        writer.finish().unwrap();
+        assert!(fs::read(&zipped).unwrap().starts_with(ZIP_MAGIC));
+        assert_eq!(read_font_file(&zipped).unwrap(), source);
+
+        let broken = dir.join("broken.flf");
+        fs::write(&broken, b"PK\x03\x04garbage").unwrap();
+        assert!(read_font_file(&broken).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
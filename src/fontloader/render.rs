@@ -0,0 +1,342 @@
+use figlet_rs::{FIGcharacter, FIGfont};
+
+const SMUSH_EQUAL: i32 = 1;
+const SMUSH_LOWLINE: i32 = 2;
+const SMUSH_HIERARCHY: i32 = 4;
+const SMUSH_PAIR: i32 = 8;
+const SMUSH_BIGX: i32 = 16;
+const SMUSH_HARDBLANK: i32 = 32;
+const LAYOUT_KERN: i32 = 64;
+const LAYOUT_SMUSH: i32 = 128;
+
+const HIERARCHY_CLASSES: [&str; 6] = ["|", "/\\", "[]", "{}", "()", "<>"];
+
+/// Horizontal layout from the font header, as described in the FIGfont spec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    FullWidth,
+    Kerning,
+    /// Smushing with the rule bits 1-32, universal smushing when zero.
+    Smushing(i32),
+}
+
+impl Layout {
+    fn from_font(font: &FIGfont) -> Self {
+        let header = &font.header_line;
+        match header.full_layout {
+            Some(full) if full & LAYOUT_SMUSH != 0 => Layout::Smushing(full & 63),
+            Some(full) if full & LAYOUT_KERN != 0 => Layout::Kerning,
+            Some(_) => Layout::FullWidth,
+            None => match header.old_layout {
+                layout if layout < 0 => Layout::FullWidth,
+                0 => Layout::Kerning,
+                layout => Layout::Smushing(layout & 63),
+            },
+        }
+    }
+}
+
+fn hierarchy_class(c: char) -> Option<usize> {
+    HIERARCHY_CLASSES.iter().position(|class| class.contains(c))
+}
+
+/// Combines two overlapping sub-characters, `None` if they may not be smushed.
+fn smush(left: char, right: char, layout: Layout, hardblank: char) -> Option<char> {
+    if left == ' ' {
+        return Some(right);
+    }
+    if right == ' ' {
+        return Some(left);
+    }
+    let Layout::Smushing(rules) = layout else {
+        return None;
+    };
+
+    if rules == 0 {
+        return Some(if left == hardblank {
+            right
+        } else if right == hardblank {
+            left
+        } else {
+            right
+        });
+    }
+    if rules & SMUSH_HARDBLANK != 0 && left == hardblank && right == hardblank {
+        return Some(left);
+    }
+    if left == hardblank || right == hardblank {
+        return None;
+    }
+    if rules & SMUSH_EQUAL != 0 && left == right {
+        return Some(left);
+    }
+    if rules & SMUSH_LOWLINE != 0 {
+        if left == '_' && "|/\\[]{}()<>".contains(right) {
+            return Some(right);
+        }
+        if right == '_' && "|/\\[]{}()<>".contains(left) {
+            return Some(left);
+        }
+    }
+    if rules & SMUSH_HIERARCHY != 0
+        && let (Some(left_class), Some(right_class)) =
+            (hierarchy_class(left), hierarchy_class(right))
+    {
+        if left_class < right_class {
+            return Some(right);
+        }
+        if right_class < left_class {
+            return Some(left);
+        }
+    }
+    if rules & SMUSH_PAIR != 0
+        && matches!(
+            (left, right),
+            ('[', ']') | (']', '[') | ('{', '}') | ('}', '{') | ('(', ')') | (')', '(')
+        )
+    {
+        return Some('|');
+    }
+    if rules & SMUSH_BIGX != 0 {
+        match (left, right) {
+            ('/', '\\') => return Some('|'),
+            ('\\', '/') => return Some('Y'),
+            ('>', '<') => return Some('X'),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Glyph rows padded to a common width so the output stays rectangular.
+fn glyph_rows(glyph: &FIGcharacter, height: usize) -> Vec<Vec<char>> {
+    let mut rows: Vec<Vec<char>> = (0..height)
+        .map(|row| {
+            glyph
+                .characters
+                .get(row)
+                .map(|line| line.chars().collect())
+                .unwrap_or_default()
+        })
+        .collect();
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    for row in &mut rows {
+        row.resize(width, ' ');
+    }
+    rows
+}
+
+/// How many columns the next glyph can slide left into the output.
+fn overlap(output: &[Vec<char>], glyph: &[Vec<char>], layout: Layout, hardblank: char) -> usize {
+    if layout == Layout::FullWidth {
+        return 0;
+    }
+    let output_width = output.first().map_or(0, Vec::len);
+    let glyph_width = glyph.first().map_or(0, Vec::len);
+    let mut amount = output_width.min(glyph_width);
+    for (left, right) in output.iter().zip(glyph) {
+        let trailing = left.iter().rev().take_while(|c| **c == ' ').count();
+        let leading = right.iter().take_while(|c| **c == ' ').count();
+        let mut row_amount = trailing + leading;
+        match (left.iter().rev().nth(trailing), right.get(leading)) {
+            (None, _) => row_amount += 1,
+            (Some(l), Some(r)) if smush(*l, *r, layout, hardblank).is_some() => row_amount += 1,
+            _ => {}
+        }
+        amount = amount.min(row_amount);
+    }
+    amount
+}
+
+/// Rendered big text as a grid of sub-characters.
+#[derive(Debug, Clone, Default)]
+pub struct Rendered {
+    pub rows: Vec<Vec<char>>,
+    /// Index of the input character each column belongs to. Smushed columns
+    /// belong to the later character.
+    pub owners: Vec<usize>,
+}
+
+impl Rendered {
+    /// A single row of plain text, one column per character.
+    pub fn plain(text: &str) -> Self {
+        let row: Vec<char> = text.chars().collect();
+        Self {
+            owners: (0..row.len()).collect(),
+            rows: vec![row],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.owners.len()
+    }
+
+    pub fn is_blank(&self) -> bool {
+        self.rows.iter().flatten().all(|c| *c == ' ')
+    }
+}
+
+/// Renders `text` with the font's horizontal layout (full width, kerning or
+/// smushing). Characters missing from the font are skipped.
+pub fn render(font: &FIGfont, text: &str) -> Rendered {
+    let height = font.header_line.height.max(0) as usize;
+    let hardblank = font.header_line.hardblank;
+    let layout = Layout::from_font(font);
+
+    let mut output: Vec<Vec<char>> = vec![Vec::new(); height];
+    let mut owners: Vec<usize> = Vec::new();
+    for (index, c) in text.chars().enumerate() {
+        let Some(glyph) = font.fonts.get(&(c as u32)) else {
+            continue;
+        };
+        let glyph = glyph_rows(glyph, height);
+        let amount = overlap(&output, &glyph, layout, hardblank);
+        let start = owners.len() - amount;
+        for (left, right) in output.iter_mut().zip(&glyph) {
+            for (offset, r) in right.iter().take(amount).enumerate() {
+                let l = left[start + offset];
+                left[start + offset] = smush(l, *r, layout, hardblank).unwrap_or(*r);
+                if *r != ' ' {
+                    owners[start + offset] = index;
+                }
+            }
+            left.extend_from_slice(&right[amount..]);
+        }
+        let glyph_width = glyph.first().map_or(0, Vec::len);
+        owners.extend(std::iter::repeat_n(index, glyph_width - amount));
+    }
+
+    for row in &mut output {
+        for c in row.iter_mut() {
+            if *c == hardblank {
+                *c = ' ';
+            }
+        }
+    }
+    Rendered {
+        rows: output,
+        owners,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use figlet_rs::HeaderLine;
+    use std::collections::HashMap;
+
+    fn smushing(rules: i32) -> Layout {
+        Layout::Smushing(rules)
+    }
+
+    #[test]
+    fn equal_character_rule() {
+        assert_eq!(smush('|', '|', smushing(SMUSH_EQUAL), '$'), Some('|'));
+        assert_eq!(smush('|', '/', smushing(SMUSH_EQUAL), '$'), None);
+    }
+
+    #[test]
+    fn underscore_rule() {
+        let layout = smushing(SMUSH_LOWLINE);
+        assert_eq!(smush('_', '/', layout, '$'), Some('/'));
+        assert_eq!(smush('[', '_', layout, '$'), Some('['));
+        assert_eq!(smush('_', 'x', layout, '$'), None);
+    }
+
+    #[test]
+    fn hierarchy_rule() {
+        let layout = smushing(SMUSH_HIERARCHY);
+        assert_eq!(smush('|', '/', layout, '$'), Some('/'));
+        assert_eq!(smush('<', '[', layout, '$'), Some('<'));
+        assert_eq!(smush('/', '\\', layout, '$'), None);
+    }
+
+    #[test]
+    fn opposite_pair_rule() {
+        let layout = smushing(SMUSH_PAIR);
+        assert_eq!(smush('[', ']', layout, '$'), Some('|'));
+        assert_eq!(smush(')', '(', layout, '$'), Some('|'));
+        assert_eq!(smush('<', '>', layout, '$'), None);
+    }
+
+    #[test]
+    fn big_x_rule() {
+        let layout = smushing(SMUSH_BIGX);
+        assert_eq!(smush('/', '\\', layout, '$'), Some('|'));
+        assert_eq!(smush('\\', '/', layout, '$'), Some('Y'));
+        assert_eq!(smush('>', '<', layout, '$'), Some('X'));
+        assert_eq!(smush('<', '>', layout, '$'), None);
+    }
+
+    #[test]
+    fn hardblank_rule() {
+        assert_eq!(smush('$', '$', smushing(SMUSH_HARDBLANK), '$'), Some('$'));
+        // Hardblanks block every other rule.
+        assert_eq!(smush('$', '|', smushing(63 & !SMUSH_HARDBLANK), '$'), None);
+        assert_eq!(smush('$', '$', smushing(SMUSH_EQUAL), '$'), None);
+    }
+
+    #[test]
+    fn universal_smushing_and_kerning() {
+        assert_eq!(smush('a', 'b', smushing(0), '$'), Some('b'));
+        assert_eq!(smush('$', 'b', smushing(0), '$'), Some('b'));
+        assert_eq!(smush('a', 'b', Layout::Kerning, '$'), None);
+        assert_eq!(smush(' ', 'b', Layout::Kerning, '$'), Some('b'));
+    }
+
+    /// A one row font with the given glyphs and `full_layout`.
+    fn font(glyphs: &[(char, &str)], full_layout: i32) -> FIGfont {
+        let fonts = glyphs
+            .iter()
+            .map(|(c, row)| {
+                let code = *c as u32;
+                let glyph = FIGcharacter {
+                    code,
+                    characters: vec![row.to_string()],
+                    width: row.chars().count() as u32,
+                    height: 1,
+                };
+                (code, glyph)
+            })
+            .collect::<HashMap<_, _>>();
+        FIGfont {
+            header_line: HeaderLine {
+                header_line: String::new(),
+                signature: "flf2a".to_string(),
+                hardblank: '$',
+                height: 1,
+                baseline: 1,
+                max_length: 4,
+                old_layout: 0,
+                comment_lines: 0,
+                print_direction: None,
+                full_layout: Some(full_layout),
+                codetag_count: None,
+            },
+            comments: String::new(),
+            fonts,
+        }
+    }
+
+    fn row(rendered: &Rendered) -> String {
+        rendered.rows[0].iter().collect()
+    }
+
+    #[test]
+    fn renders_each_layout() {
+        let glyphs = [('a', "a| "), ('b', " |b"), ('c', "c$")];
+        let full = render(&font(&glyphs, 0), "ab");
+        assert_eq!(row(&full), "a|  |b");
+
+        let kerned = render(&font(&glyphs, LAYOUT_KERN), "ab");
+        assert_eq!(row(&kerned), "a||b");
+
+        let smushed = render(&font(&glyphs, LAYOUT_SMUSH | SMUSH_EQUAL), "ab");
+        assert_eq!(row(&smushed), "a|b");
+        assert_eq!(smushed.owners, vec![0, 1, 1]);
+
+        // Hardblanks keep their column and render as spaces.
+        let hardblank = render(&font(&glyphs, LAYOUT_SMUSH | SMUSH_EQUAL), "cz a");
+        assert_eq!(row(&hardblank), "c a| ");
+    }
+}
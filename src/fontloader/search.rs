@@ -0,0 +1,109 @@
+use crate::paths;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Fonts compiled into the binary so the panel works without a fonts directory.
+const EMBEDDED_FONTS: [(&str, &str); 2] = [
+    ("small", include_str!("../../fonts/small.flf")),
+    ("large", include_str!("../../fonts/large.flf")),
+];
+
+const SYSTEM_FONT_DIRS: [&str; 4] = [
+    "/usr/share/figlet",
+    "/usr/share/figlet/fonts",
+    "/usr/local/share/figlet",
+    "/usr/share/figlet-fonts",
+];
+
+const FONT_EXTENSIONS: [&str; 2] = ["flf", "tlf"];
+const CONTROL_EXTENSIONS: [&str; 1] = ["flc"];
+
+/// Where a font name resolved to.
+#[derive(Debug, Clone)]
+pub enum FontSource {
+    File(PathBuf),
+    Embedded(&'static str),
+}
+
+impl FontSource {
+    pub fn describe(&self) -> String {
+        match self {
+            FontSource::File(path) => path.display().to_string(),
+            FontSource::Embedded(_) => "(embedded)".to_string(),
+        }
+    }
+}
+
+/// Directories searched for fonts, in priority order: `./fonts`, the config
+/// dir, the data dir, then the usual system figlet locations.
+pub fn search_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![
+        PathBuf::from("fonts"),
+        paths::config_dir().join("fonts"),
+        paths::data_dir().join("fonts"),
+    ];
+    dirs.extend(SYSTEM_FONT_DIRS.iter().map(PathBuf::from));
+    dirs
+}
+
+fn embedded(name: &str) -> Option<FontSource> {
+    EMBEDDED_FONTS
+        .iter()
+        .find(|(embedded_name, _)| *embedded_name == name)
+        .map(|(_, content)| FontSource::Embedded(content))
+}
+
+/// A path as given, with `~/` expanded, or the first `name.<extension>` found
+/// in `search_dirs`.
+fn find_file(name: &str, extensions: &[&str]) -> Option<PathBuf> {
+    if name.contains('/') {
+        let path = paths::expand_home(name);
+        return path.is_file().then_some(path);
+    }
+    search_dirs()
+        .iter()
+        .flat_map(|dir| {
+            extensions
+                .iter()
+                .map(move |extension| dir.join(format!("{}.{}", name, extension)))
+        })
+        .find(|path| path.is_file())
+}
+
+/// Resolves a font name ("small") or path ("~/fonts/big.tlf") to its source.
+/// Names are looked up in `search_dirs` before the embedded fonts.
+pub fn resolve(name: &str) -> Option<FontSource> {
+    find_file(name, &FONT_EXTENSIONS)
+        .map(FontSource::File)
+        .or_else(|| embedded(name))
+}
+
+/// Resolves a FIGlet control file name ("utf8") or path to a `.flc` file.
+pub fn resolve_control(name: &str) -> Option<PathBuf> {
+    find_file(name, &CONTROL_EXTENSIONS)
+}
+
+fn font_name(path: &Path) -> Option<String> {
+    let extension = path.extension()?.to_str()?;
+    if !FONT_EXTENSIONS.contains(&extension) {
+        return None;
+    }
+    Some(path.file_stem()?.to_string_lossy().to_string())
+}
+
+/// Every font name that `resolve` can find, with the source it resolves to.
+pub fn list_fonts() -> Vec<(String, FontSource)> {
+    let mut names: Vec<String> = search_dirs()
+        .iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten())
+        .filter_map(|entry| font_name(&entry.path()))
+        .chain(EMBEDDED_FONTS.iter().map(|(name, _)| name.to_string()))
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+        .into_iter()
+        .filter_map(|name| resolve(&name).map(|source| (name, source)))
+        .collect()
+}
@@ -0,0 +1,57 @@
+/// Shell style wildcard match supporting `*` and `?`. A pattern without
+/// wildcards must match the whole label.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_literals_exactly() {
+        assert!(glob_match("k10temp Tctl", "k10temp Tctl"));
+        assert!(!glob_match("Tctl", "k10temp Tctl"));
+        assert!(!glob_match("k10temp", "k10temp Tctl"));
+        assert!(glob_match("", ""));
+    }
+
+    #[test]
+    fn matches_wildcards() {
+        assert!(glob_match("*Tctl", "k10temp Tctl"));
+        assert!(glob_match("coretemp Package*", "coretemp Package id 0"));
+        assert!(glob_match("*Package*", "coretemp Package id 0"));
+        assert!(glob_match("wl?n0", "wlan0"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("?", ""));
+        assert!(!glob_match("wl?n0", "wlaan0"));
+    }
+
+    #[test]
+    fn backtracks_over_stars() {
+        assert!(glob_match("*a*b", "xaxxab"));
+        assert!(glob_match("a*b*c", "abbbc"));
+        assert!(!glob_match("a*b*c", "abbb"));
+        assert!(glob_match("**x", "yyx"));
+    }
+}
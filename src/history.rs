@@ -0,0 +1,34 @@
+use std::collections::VecDeque;
+
+/// Fixed size ring buffer of the most recent samples of one metric.
+#[derive(Debug, Clone)]
+pub struct History {
+    samples: VecDeque<u64>,
+    capacity: usize,
+}
+
+impl History {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+        }
+    }
+
+    pub fn push(&mut self, value: u64) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(value);
+    }
+
+    /// The newest `count` samples, oldest first.
+    pub fn tail(&self, count: usize) -> Vec<u64> {
+        let skip = self.samples.len().saturating_sub(count);
+        self.samples.iter().skip(skip).copied().collect()
+    }
+
+    pub fn peak(&self) -> u64 {
+        self.samples.iter().copied().max().unwrap_or(0)
+    }
+}
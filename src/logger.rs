@@ -1,41 +1,346 @@
-use std::fs::OpenOptions;
+use crate::config::{LogLevel, LoggingConfig};
+use std::collections::{HashMap, VecDeque};
+use std::fs::{self, File, OpenOptions};
 use std::io::Write;
-use std::sync::Mutex;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-pub struct Logger {
-    log_file: Mutex<Option<std::fs::File>>,
+/// Messages kept in memory for the log viewer.
+const RING_CAPACITY: usize = 500;
+/// Identical messages from the same target are written at most once per window.
+const REPEAT_WINDOW: Duration = Duration::from_secs(60);
+
+impl LogLevel {
+    pub fn label(self) -> &'static str {
+        match self {
+            LogLevel::Error => "ERROR",
+            LogLevel::Warn => "WARN",
+            LogLevel::Info => "INFO",
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Trace => "TRACE",
+        }
+    }
 }
 
-impl Logger {
-    pub fn new(log_file_path: &str) -> Self {
-        let log_file = OpenOptions::new()
+/// The open log file and what is needed to rotate it.
+struct LogFile {
+    file: File,
+    path: PathBuf,
+    size: u64,
+    max_size: u64,
+    max_files: usize,
+}
+
+impl LogFile {
+    fn open(path: &Path, max_size: u64, max_files: usize) -> Option<Self> {
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        let file = OpenOptions::new()
             .create(true)
             .append(true)
-            .open(log_file_path)
-            .ok();
+            .open(path)
+            .ok()?;
+        let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+        Some(Self {
+            file,
+            path: path.to_path_buf(),
+            size,
+            max_size,
+            max_files,
+        })
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+
+    /// Shifts `log.1..log.N-1` up by one, moves the current file to `log.1`
+    /// and starts a new one. Without rotated files the log is truncated.
+    fn rotate(&mut self) {
+        if self.max_files > 0 {
+            let _ = fs::remove_file(self.rotated_path(self.max_files));
+            for index in (1..self.max_files).rev() {
+                let _ = fs::rename(self.rotated_path(index), self.rotated_path(index + 1));
+            }
+            let _ = fs::rename(&self.path, self.rotated_path(1));
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path);
+        if let Ok(file) = file {
+            self.file = file;
+            self.size = 0;
+        }
+    }
+
+    fn write(&mut self, line: &str) {
+        if self.max_size > 0 && self.size + line.len() as u64 > self.max_size {
+            self.rotate();
+        }
+        if self.file.write_all(line.as_bytes()).is_ok() {
+            self.size += line.len() as u64;
+        }
+    }
+}
+
+/// A logged message as kept in the ring buffer.
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub timestamp: String,
+    pub level: LogLevel,
+    pub target: &'static str,
+    pub message: String,
+}
+
+/// A message as told apart for coalescing: target, level and text.
+type RepeatKey = (&'static str, LogLevel, String);
+
+/// When a message was last written and how often it was dropped since.
+struct Repeat {
+    written: Instant,
+    suppressed: u32,
+}
+
+struct Shared {
+    log_file: Mutex<Option<LogFile>>,
+    recent: Mutex<VecDeque<LogEntry>>,
+    repeats: Mutex<HashMap<RepeatKey, Repeat>>,
+    level: LogLevel,
+    /// Whether messages may go to stderr when there is no log file. Off while
+    /// the TUI owns the terminal.
+    console: AtomicBool,
+}
+
+/// Leveled logger writing to a rotated file. Each widget gets its own target
+/// through `for_target` so log lines show where they came from.
+pub struct Logger {
+    target: &'static str,
+    shared: Arc<Shared>,
+}
+
+impl Logger {
+    pub fn new(config: &LoggingConfig) -> Self {
+        let path = config
+            .file
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| crate::paths::state_dir().join("gjpanel.log"));
+        let log_file = LogFile::open(&path, config.max_size_kb * 1024, config.max_files);
 
         Logger {
-            log_file: Mutex::new(log_file),
+            target: "gjpanel",
+            shared: Arc::new(Shared {
+                log_file: Mutex::new(log_file),
+                recent: Mutex::new(VecDeque::with_capacity(RING_CAPACITY)),
+                repeats: Mutex::new(HashMap::new()),
+                level: config.level,
+                console: AtomicBool::new(true),
+            }),
         }
     }
 
-    pub fn info(&self, message: &str) {
-        self.log("INFO", message);
+    /// A logger sharing this one's file and level, tagged with `target`.
+    /// Loggers live for the whole program, so the new one is leaked.
+    pub fn for_target(&self, target: &'static str) -> &'static Logger {
+        Box::leak(Box::new(Logger {
+            target,
+            shared: Arc::clone(&self.shared),
+        }))
+    }
+
+    /// Allows or forbids the stderr fallback, e.g. while the TUI is drawn.
+    pub fn set_console(&self, enabled: bool) {
+        self.shared.console.store(enabled, Ordering::Relaxed);
+    }
+
+    /// The most recent messages, oldest first.
+    pub fn recent(&self) -> Vec<LogEntry> {
+        self.shared.recent.lock().unwrap().iter().cloned().collect()
     }
 
     pub fn error(&self, message: &str) {
-        self.log("ERROR", message);
+        self.log(LogLevel::Error, message);
+    }
+
+    pub fn warn(&self, message: &str) {
+        self.log(LogLevel::Warn, message);
+    }
+
+    pub fn info(&self, message: &str) {
+        self.log(LogLevel::Info, message);
+    }
+
+    pub fn debug(&self, message: &str) {
+        self.log(LogLevel::Debug, message);
+    }
+
+    pub fn trace(&self, message: &str) {
+        self.log(LogLevel::Trace, message);
     }
 
-    fn log(&self, level: &str, message: &str) {
+    /// Returns false and counts the message if it was already written within
+    /// `REPEAT_WINDOW`. Also returns the messages whose window has run out
+    /// with copies dropped, oldest first, so their counts are not lost.
+    fn coalesce(&self, level: LogLevel, message: &str) -> (bool, Vec<(RepeatKey, u32)>) {
+        let mut repeats = self.shared.repeats.lock().unwrap();
+        let mut expired = Vec::new();
+        repeats.retain(|key, repeat| {
+            if repeat.written.elapsed() < REPEAT_WINDOW {
+                return true;
+            }
+            if repeat.suppressed > 0 {
+                expired.push((repeat.written, key.clone(), repeat.suppressed));
+            }
+            false
+        });
+        expired.sort_by_key(|(written, _, _)| *written);
+
+        let key = (self.target, level, message.to_string());
+        let write = match repeats.get_mut(&key) {
+            Some(repeat) => {
+                repeat.suppressed += 1;
+                false
+            }
+            None => {
+                repeats.insert(
+                    key,
+                    Repeat {
+                        written: Instant::now(),
+                        suppressed: 0,
+                    },
+                );
+                true
+            }
+        };
+        let expired = expired
+            .into_iter()
+            .map(|(_, key, suppressed)| (key, suppressed))
+            .collect();
+        (write, expired)
+    }
+
+    fn log(&self, level: LogLevel, message: &str) {
+        if level > self.shared.level {
+            return;
+        }
+        let (write, expired) = self.coalesce(level, message);
+        for ((target, level, message), suppressed) in expired {
+            self.write(
+                target,
+                level,
+                format!("{} (repeated {} times)", message, suppressed),
+            );
+        }
+        if write {
+            self.write(self.target, level, message.to_string());
+        }
+    }
+
+    fn write(&self, target: &'static str, level: LogLevel, message: String) {
         let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
-        let log_message = format!("[{}] [{}] {}\n", timestamp, level, message);
+        let log_message = format!(
+            "[{}] [{}] [{}] {}\n",
+            timestamp,
+            level.label(),
+            target,
+            message
+        );
 
-        if let Some(file) = self.log_file.lock().unwrap().as_mut() {
-            let _ = file.write_all(log_message.as_bytes());
-        } else {
-            // Fallback to stdout if file logging fails
-            println!("{}", log_message);
+        if let Some(file) = self.shared.log_file.lock().unwrap().as_mut() {
+            file.write(&log_message);
+        } else if self.shared.console.load(Ordering::Relaxed) {
+            // Fallback to stderr if file logging fails and the TUI is not active
+            eprint!("{}", log_message);
         }
+
+        let mut recent = self.shared.recent.lock().unwrap();
+        if recent.len() == RING_CAPACITY {
+            recent.pop_front();
+        }
+        recent.push_back(LogEntry {
+            timestamp,
+            level,
+            target,
+            message,
+        });
+    }
+}
+
+/// A logger writing to a file in the temp dir, for widgets built in tests.
+#[cfg(test)]
+pub fn test_logger() -> &'static Logger {
+    let config = LoggingConfig {
+        file: Some(
+            std::env::temp_dir()
+                .join("gjpanel-test.log")
+                .to_string_lossy()
+                .into_owned(),
+        ),
+        ..LoggingConfig::default()
+    };
+    Box::leak(Box::new(Logger::new(&config)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(logger: &Logger) -> Vec<String> {
+        logger
+            .recent()
+            .into_iter()
+            .map(|entry| entry.message)
+            .collect()
+    }
+
+    /// Moves every coalesced message's window into the past.
+    fn expire_windows(logger: &Logger) {
+        let past = Instant::now() - REPEAT_WINDOW - Duration::from_secs(1);
+        for repeat in logger.shared.repeats.lock().unwrap().values_mut() {
+            repeat.written = past;
+        }
+    }
+
+    #[test]
+    fn drops_repeats_within_the_window() {
+        let logger = test_logger();
+        for _ in 0..3 {
+            logger.warn("disk full");
+        }
+        assert_eq!(messages(logger), vec!["disk full"]);
+    }
+
+    #[test]
+    fn flushes_count_once_the_window_expires() {
+        let logger = test_logger();
+        for _ in 0..3 {
+            logger.warn("disk full");
+        }
+        expire_windows(logger);
+        logger.warn("something else");
+        assert_eq!(
+            messages(logger),
+            vec![
+                "disk full",
+                "disk full (repeated 2 times)",
+                "something else"
+            ]
+        );
+    }
+
+    #[test]
+    fn writes_message_again_after_the_window() {
+        let logger = test_logger();
+        logger.warn("disk full");
+        expire_windows(logger);
+        logger.warn("disk full");
+        assert_eq!(messages(logger), vec!["disk full", "disk full"]);
     }
 }
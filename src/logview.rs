@@ -0,0 +1,154 @@
+use crate::config::LogLevel;
+use crate::logger::{LogEntry, Logger};
+use crate::theme;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+const LEVELS: [LogLevel; 5] = [
+    LogLevel::Error,
+    LogLevel::Warn,
+    LogLevel::Info,
+    LogLevel::Debug,
+    LogLevel::Trace,
+];
+
+/// Overlay listing the logger's recent messages, toggled with `L` since `l`
+/// already moves the calendar a month forward.
+#[derive(Debug)]
+pub struct LogViewer {
+    /// Most verbose level shown.
+    level: LogLevel,
+    /// Only messages from this target, all when `None`.
+    target: Option<&'static str>,
+    /// Messages scrolled up from the newest one.
+    scroll: usize,
+}
+
+impl LogViewer {
+    pub fn new() -> Self {
+        Self {
+            level: LogLevel::Trace,
+            target: None,
+            scroll: 0,
+        }
+    }
+
+    fn entries(&self, logger: &Logger) -> Vec<LogEntry> {
+        logger
+            .recent()
+            .into_iter()
+            .filter(|entry| entry.level <= self.level)
+            .filter(|entry| self.target.is_none_or(|target| entry.target == target))
+            .collect()
+    }
+
+    /// Cycles through the targets that have logged, then back to all.
+    fn next_target(&mut self, logger: &Logger) {
+        let mut targets: Vec<&'static str> =
+            logger.recent().iter().map(|entry| entry.target).collect();
+        targets.sort();
+        targets.dedup();
+        self.target = match self
+            .target
+            .and_then(|t| targets.iter().position(|x| *x == t))
+        {
+            Some(i) => targets.get(i + 1).copied(),
+            None => targets.first().copied(),
+        };
+        self.scroll = 0;
+    }
+
+    /// Returns false when the viewer should close.
+    pub fn handle_key_event(&mut self, key_event: KeyEvent, logger: &Logger) -> bool {
+        match key_event.code {
+            KeyCode::Char('L') | KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('f') => {
+                let index = LEVELS.iter().position(|l| *l == self.level).unwrap_or(0);
+                self.level = LEVELS[(index + LEVELS.len() - 1) % LEVELS.len()];
+                self.scroll = 0;
+            }
+            KeyCode::Char('t') => self.next_target(logger),
+            KeyCode::Char('k') | KeyCode::Up => self.scroll += 1,
+            KeyCode::Char('j') | KeyCode::Down => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::PageUp => self.scroll += 10,
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::Char('g') | KeyCode::Home => self.scroll = usize::MAX,
+            KeyCode::Char('G') | KeyCode::End => self.scroll = 0,
+            _ => {}
+        }
+        true
+    }
+
+    fn level_style(level: LogLevel) -> Style {
+        let theme = theme::current();
+        match level {
+            LogLevel::Error => Style::new().fg(theme.critical),
+            LogLevel::Warn => Style::new().fg(theme.warning),
+            LogLevel::Info => Style::new().fg(theme.fg),
+            LogLevel::Debug => Style::new().fg(theme.muted),
+            LogLevel::Trace => Style::new().fg(theme.muted).dim(),
+        }
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect, logger: &Logger) {
+        let theme = theme::current();
+        let entries = self.entries(logger);
+        let scroll = self.scroll.min(entries.len().saturating_sub(1));
+        let end = entries.len() - scroll;
+        let width = area.width.saturating_sub(2).max(1) as usize;
+
+        // Walk back from the newest entry until the wrapped lines fill the box.
+        let mut lines: Vec<Line> = Vec::new();
+        let mut height = 0;
+        for entry in entries[..end].iter().rev() {
+            let time = entry
+                .timestamp
+                .split(' ')
+                .nth(1)
+                .unwrap_or(&entry.timestamp);
+            let line = Line::from(vec![
+                Span::styled(format!("{} ", time), Style::new().fg(theme.muted)),
+                Span::styled(
+                    format!("{:<5} ", entry.level.label()),
+                    Self::level_style(entry.level),
+                ),
+                Span::styled(format!("{} ", entry.target), Style::new().fg(theme.accent)),
+                Span::raw(entry.message.clone()),
+            ]);
+            height += line.width().max(1).div_ceil(width);
+            if height > area.height.saturating_sub(2) as usize && !lines.is_empty() {
+                break;
+            }
+            lines.push(line);
+        }
+        lines.reverse();
+
+        let title = format!(
+            " log ≤{} {}{} ",
+            self.level.label(),
+            self.target.unwrap_or("all"),
+            if scroll > 0 {
+                format!(" +{}", scroll)
+            } else {
+                String::new()
+            },
+        );
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .title_style(Style::new().fg(theme.fg).bold());
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(block)
+                .wrap(Wrap { trim: false }),
+            area,
+        );
+    }
+}
@@ -1,18 +1,51 @@
 use app::App;
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
 use logger::Logger;
 
 mod app;
+mod color;
 mod config;
+mod control;
 mod fontloader;
+mod format;
+mod glob;
+mod history;
 mod logger;
+mod logview;
+mod notify;
+mod paths;
+mod process;
+mod theme;
 mod widgets;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let logger = Box::new(Logger::new("rust-panel.log"));
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().is_some_and(|arg| arg == "ctl") {
+        let reply = control::send_command(&args[1..])?;
+        println!("{}", reply);
+        return Ok(());
+    }
+    if args.first().is_some_and(|arg| arg == "--list-fonts") {
+        for (name, source) in fontloader::list_fonts() {
+            println!("{:<20} {}", name, source.describe());
+        }
+        return Ok(());
+    }
+
+    let config = config::load_config("gjwidgets.toml");
+    let logger = Box::new(Logger::new(&config.logging));
     let logger_ref: &'static Logger = Box::leak(logger);
+    theme::init(theme::Theme::load(&config.theme, logger_ref));
 
+    logger_ref.set_console(false);
     let mut terminal = ratatui::init();
-    let res = App::default().run_app(&mut terminal, logger_ref);
+    // The terminal is restored whatever fails, so errors are only returned after it.
+    let res = match crossterm::execute!(std::io::stdout(), EnableMouseCapture) {
+        Ok(()) => App::default().run_app(&mut terminal, config, logger_ref),
+        Err(err) => Err(err.into()),
+    };
+    let _ = crossterm::execute!(std::io::stdout(), DisableMouseCapture);
     ratatui::restore();
+    logger_ref.set_console(true);
     res
 }
@@ -0,0 +1,37 @@
+use crate::logger::Logger;
+use crate::process::spawn_reaped;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Sends a desktop notification through `notify-send`, ringing the terminal bell
+/// when no notification daemon is reachable. `notify-send` runs in the
+/// background so a hung daemon can't stall the panel.
+pub fn notify(summary: &str, body: &str, logger: &'static Logger) {
+    let mut command = Command::new("notify-send");
+    command
+        .arg("--app-name=gjpanel")
+        .arg(summary)
+        .arg(body)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+
+    let owned_summary = summary.to_string();
+    let spawned = spawn_reaped(&mut command, move |sent| {
+        if !sent {
+            ring_bell(&owned_summary, logger);
+        }
+    });
+    if spawned.is_err() {
+        ring_bell(summary, logger);
+    }
+}
+
+fn ring_bell(summary: &str, logger: &Logger) {
+    logger.info(&format!(
+        "notify-send unavailable, ringing bell: {}",
+        summary
+    ));
+    let mut stdout = std::io::stdout();
+    let _ = stdout.write_all(b"\x07");
+    let _ = stdout.flush();
+}
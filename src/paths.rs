@@ -0,0 +1,44 @@
+use std::env;
+use std::path::PathBuf;
+
+const APP_DIR: &str = "gjpanel";
+
+fn xdg_dir(var: &str, home_fallback: &str) -> PathBuf {
+    match env::var(var) {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => {
+            PathBuf::from(env::var("HOME").unwrap_or_else(|_| ".".to_string())).join(home_fallback)
+        }
+    }
+}
+
+/// `$XDG_STATE_HOME/gjpanel`, for data that should survive panel restarts.
+pub fn state_dir() -> PathBuf {
+    xdg_dir("XDG_STATE_HOME", ".local/state").join(APP_DIR)
+}
+
+/// `$XDG_CONFIG_HOME/gjpanel`, for user configuration and fonts.
+pub fn config_dir() -> PathBuf {
+    xdg_dir("XDG_CONFIG_HOME", ".config").join(APP_DIR)
+}
+
+/// `$XDG_DATA_HOME/gjpanel`, for installed assets such as fonts.
+pub fn data_dir() -> PathBuf {
+    xdg_dir("XDG_DATA_HOME", ".local/share").join(APP_DIR)
+}
+
+/// `$XDG_RUNTIME_DIR`, falling back to the temp dir when unset.
+pub fn runtime_dir() -> PathBuf {
+    match env::var("XDG_RUNTIME_DIR") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => env::temp_dir(),
+    }
+}
+
+/// Expands a leading `~/` to `$HOME`, leaving other paths as they are.
+pub fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), env::var("HOME")) {
+        (Some(rest), Ok(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
@@ -0,0 +1,18 @@
+use std::io;
+use std::process::Command;
+use std::thread;
+
+/// Spawns `command` and waits for it on a background thread, so neither a slow
+/// command blocks the caller nor a finished one lingers as a zombie. `on_exit`
+/// is told whether the command exited successfully.
+pub fn spawn_reaped(
+    command: &mut Command,
+    on_exit: impl FnOnce(bool) + Send + 'static,
+) -> io::Result<()> {
+    let mut child = command.spawn()?;
+    thread::spawn(move || {
+        let success = child.wait().is_ok_and(|status| status.success());
+        on_exit(success);
+    });
+    Ok(())
+}
@@ -0,0 +1,189 @@
+use crate::color::parse_color;
+use crate::config::ThemeConfig;
+use crate::logger::Logger;
+use crate::paths;
+use ratatui::style::Color;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Semantic colors shared by all widgets.
+#[derive(Debug, Clone, Copy)]
+pub struct Theme {
+    /// Regular text.
+    pub fg: Color,
+    /// Labels, secondary text and inactive items.
+    pub muted: Color,
+    /// Highlights such as the clock and the active workspace.
+    pub accent: Color,
+    pub warning: Color,
+    pub critical: Color,
+    /// Behind bars and gauges.
+    pub background: Color,
+    /// Borders and empty cells.
+    pub border: Color,
+}
+
+impl Default for Theme {
+    /// The terminal's own palette, matching the colors used before themes.
+    fn default() -> Self {
+        Self {
+            fg: Color::Gray,
+            muted: Color::DarkGray,
+            accent: Color::Blue,
+            warning: Color::Yellow,
+            critical: Color::Red,
+            background: Color::Black,
+            border: Color::DarkGray,
+        }
+    }
+}
+
+impl Theme {
+    fn builtin(name: &str) -> Option<Self> {
+        let rgb = |hex: u32| Color::Rgb((hex >> 16) as u8, (hex >> 8) as u8, hex as u8);
+        match name {
+            "default" => Some(Self::default()),
+            // Catppuccin Mocha
+            "catppuccin" => Some(Self {
+                fg: rgb(0xcdd6f4),
+                muted: rgb(0x6c7086),
+                accent: rgb(0x89b4fa),
+                warning: rgb(0xf9e2af),
+                critical: rgb(0xf38ba8),
+                background: rgb(0x1e1e2e),
+                border: rgb(0x45475a),
+            }),
+            // Gruvbox dark
+            "gruvbox" => Some(Self {
+                fg: rgb(0xebdbb2),
+                muted: rgb(0x928374),
+                accent: rgb(0x83a598),
+                warning: rgb(0xfabd2f),
+                critical: rgb(0xfb4934),
+                background: rgb(0x282828),
+                border: rgb(0x504945),
+            }),
+            "nord" => Some(Self {
+                fg: rgb(0xd8dee9),
+                muted: rgb(0x616e88),
+                accent: rgb(0x88c0d0),
+                warning: rgb(0xebcb8b),
+                critical: rgb(0xbf616a),
+                background: rgb(0x2e3440),
+                border: rgb(0x434c5e),
+            }),
+            _ => None,
+        }
+    }
+
+    fn file_path(name: &str) -> PathBuf {
+        if name.contains('/') || name.ends_with(".toml") {
+            PathBuf::from(name)
+        } else {
+            paths::config_dir()
+                .join("themes")
+                .join(format!("{}.toml", name))
+        }
+    }
+
+    /// Parses a theme file: the `[theme]` keys either at the top level or
+    /// under a `[theme]` header, with `name` the built-in palette it starts from.
+    fn parse_file(content: &str) -> Result<ThemeConfig, toml::de::Error> {
+        let mut table: toml::Table = toml::from_str(content)?;
+        match table.remove("theme") {
+            Some(toml::Value::Table(theme)) => theme.try_into(),
+            _ => table.try_into(),
+        }
+    }
+
+    fn load_file(name: &str, logger: &Logger) -> Option<Self> {
+        let path = Self::file_path(name);
+        let content = fs::read_to_string(&path).ok()?;
+        match Self::parse_file(&content) {
+            Ok(config) => {
+                let mut theme = Self::builtin(&config.name).unwrap_or_else(|| {
+                    logger.warn(&format!(
+                        "Theme {} is based on unknown palette '{}'",
+                        path.display(),
+                        config.name
+                    ));
+                    Self::default()
+                });
+                theme.apply_overrides(&config, logger);
+                Some(theme)
+            }
+            Err(e) => {
+                logger.error(&format!("Invalid theme {}: {}", path.display(), e));
+                None
+            }
+        }
+    }
+
+    fn apply_overrides(&mut self, config: &ThemeConfig, logger: &Logger) {
+        let overrides = [
+            (&mut self.fg, &config.fg),
+            (&mut self.muted, &config.muted),
+            (&mut self.accent, &config.accent),
+            (&mut self.warning, &config.warning),
+            (&mut self.critical, &config.critical),
+            (&mut self.background, &config.background),
+            (&mut self.border, &config.border),
+        ];
+        for (color, value) in overrides {
+            if let Some(value) = value {
+                match parse_color(value) {
+                    Some(parsed) => *color = parsed,
+                    None => logger.warn(&format!("Invalid theme color '{}'", value)),
+                }
+            }
+        }
+    }
+
+    /// Resolves the configured palette or theme file, then applies the
+    /// per-color overrides from `[theme]`.
+    pub fn load(config: &ThemeConfig, logger: &Logger) -> Self {
+        let mut theme = Self::builtin(&config.name)
+            .or_else(|| Self::load_file(&config.name, logger))
+            .unwrap_or_else(|| {
+                logger.error(&format!(
+                    "Theme '{}' not found, using the default palette",
+                    config.name
+                ));
+                Self::default()
+            });
+        theme.apply_overrides(config, logger);
+        theme
+    }
+}
+
+/// Sets the theme returned by `current`. Only the first call has an effect.
+pub fn init(theme: Theme) {
+    let _ = THEME.set(theme);
+}
+
+/// The panel's theme, the default palette until `init` is called.
+pub fn current() -> &'static Theme {
+    THEME.get_or_init(Theme::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_theme_files_with_or_without_header() {
+        let flat = Theme::parse_file("name = \"nord\"\naccent = \"#ff0000\"\n").unwrap();
+        assert_eq!(flat.name, "nord");
+        assert_eq!(flat.accent.as_deref(), Some("#ff0000"));
+
+        let table = Theme::parse_file("[theme]\nname = \"gruvbox\"\nfg = \"white\"\n").unwrap();
+        assert_eq!(table.name, "gruvbox");
+        assert_eq!(table.fg.as_deref(), Some("white"));
+
+        assert_eq!(Theme::parse_file("").unwrap().name, "default");
+        assert!(Theme::parse_file("name = 3").is_err());
+    }
+}
@@ -0,0 +1,184 @@
+use crate::config::AlarmConfig;
+use chrono::{Datelike, NaiveDateTime, NaiveTime, TimeDelta, Weekday};
+
+/// How far back the first check after startup looks for due alarms.
+const STARTUP_GRACE: TimeDelta = TimeDelta::minutes(1);
+
+#[derive(Debug, Clone)]
+pub struct Alarm {
+    pub time: NaiveTime,
+    /// Days the alarm repeats on, every day when empty.
+    pub weekdays: Vec<Weekday>,
+    pub label: String,
+    pub command: Option<String>,
+    pub notify: bool,
+    /// Added through the control socket rather than the config file.
+    pub runtime: bool,
+}
+
+impl Alarm {
+    pub fn from_config(config: &AlarmConfig) -> Result<Self, String> {
+        let weekdays = config
+            .weekdays
+            .iter()
+            .map(|day| {
+                day.parse::<Weekday>()
+                    .map_err(|_| format!("invalid weekday '{}'", day))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            time: parse_time(&config.time)?,
+            weekdays,
+            label: config.label.clone(),
+            command: config.command.clone(),
+            notify: config.notify,
+            runtime: false,
+        })
+    }
+
+    pub fn repeats_on(&self, day: Weekday) -> bool {
+        self.weekdays.is_empty() || self.weekdays.contains(&day)
+    }
+
+    pub fn describe(&self) -> String {
+        let days = if self.weekdays.is_empty() {
+            "daily".to_string()
+        } else {
+            self.weekdays
+                .iter()
+                .map(|day| day.to_string().chars().take(2).collect::<String>())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        format!("{} {} {}", self.time.format("%H:%M"), days, self.label)
+            .trim_end()
+            .to_string()
+    }
+}
+
+pub fn parse_time(value: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(value, "%H:%M:%S"))
+        .map_err(|_| format!("invalid alarm time '{}'", value))
+}
+
+/// Finds alarms that became due between two checks. Takes the current time as
+/// an argument so firing can be driven by any clock source.
+#[derive(Debug, Default)]
+pub struct AlarmScheduler {
+    pub alarms: Vec<Alarm>,
+    last_check: Option<NaiveDateTime>,
+}
+
+impl AlarmScheduler {
+    pub fn new(alarms: Vec<Alarm>) -> Self {
+        Self {
+            alarms,
+            last_check: None,
+        }
+    }
+
+    /// Returns the alarms whose occurrence falls in `(last check, now]`.
+    pub fn due(&mut self, now: NaiveDateTime) -> Vec<Alarm> {
+        let since = self.last_check.unwrap_or(now - STARTUP_GRACE);
+        self.last_check = Some(now);
+        if since >= now {
+            return Vec::new();
+        }
+
+        self.alarms
+            .iter()
+            .filter(|alarm| {
+                let mut day = since.date();
+                while day <= now.date() {
+                    let occurrence = day.and_time(alarm.time);
+                    if alarm.repeats_on(day.weekday()) && since < occurrence && occurrence <= now {
+                        return true;
+                    }
+                    day = match day.succ_opt() {
+                        Some(next) => next,
+                        None => break,
+                    };
+                }
+                false
+            })
+            .cloned()
+            .collect()
+    }
+
+    pub fn add(&mut self, alarm: Alarm) {
+        self.alarms.push(alarm);
+        self.alarms.sort_by_key(|alarm| alarm.time);
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<Alarm> {
+        (index < self.alarms.len()).then(|| self.alarms.remove(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(day: u32, hour: u32, minute: u32, second: u32) -> NaiveDateTime {
+        // October 2026: the 19th is a Monday
+        NaiveDate::from_ymd_opt(2026, 10, day)
+            .and_then(|date| date.and_hms_opt(hour, minute, second))
+            .unwrap()
+    }
+
+    fn alarm(time: &str, weekdays: &[Weekday]) -> Alarm {
+        Alarm {
+            time: parse_time(time).unwrap(),
+            weekdays: weekdays.to_vec(),
+            label: String::new(),
+            command: None,
+            notify: false,
+            runtime: false,
+        }
+    }
+
+    #[test]
+    fn fires_exactly_at_alarm_time() {
+        let mut scheduler = AlarmScheduler::new(vec![alarm("08:00", &[])]);
+        assert!(scheduler.due(at(19, 7, 59, 59)).is_empty());
+        assert_eq!(scheduler.due(at(19, 8, 0, 0)).len(), 1);
+    }
+
+    #[test]
+    fn respects_weekdays() {
+        let mut scheduler = AlarmScheduler::new(vec![alarm("08:00", &[Weekday::Tue])]);
+        scheduler.due(at(19, 7, 59, 0));
+        assert!(scheduler.due(at(19, 8, 0, 30)).is_empty());
+        scheduler.due(at(20, 7, 59, 0));
+        assert_eq!(scheduler.due(at(20, 8, 0, 30)).len(), 1);
+    }
+
+    #[test]
+    fn fires_across_midnight() {
+        let mut scheduler =
+            AlarmScheduler::new(vec![alarm("23:59:59", &[]), alarm("00:00:30", &[])]);
+        scheduler.due(at(19, 23, 59, 50));
+        assert_eq!(scheduler.due(at(20, 0, 1, 0)).len(), 2);
+    }
+
+    #[test]
+    fn does_not_fire_twice() {
+        let mut scheduler = AlarmScheduler::new(vec![alarm("08:00", &[])]);
+        scheduler.due(at(19, 7, 59, 59));
+        assert_eq!(scheduler.due(at(19, 8, 0, 0)).len(), 1);
+        assert!(scheduler.due(at(19, 8, 0, 0)).is_empty());
+        assert!(scheduler.due(at(19, 8, 0, 1)).is_empty());
+        assert!(scheduler.due(at(19, 9, 0, 0)).is_empty());
+    }
+
+    #[test]
+    fn startup_grace_window() {
+        let mut late = AlarmScheduler::new(vec![alarm("08:00", &[])]);
+        assert_eq!(late.due(at(19, 8, 0, 40)).len(), 1);
+
+        let mut too_late = AlarmScheduler::new(vec![alarm("08:00", &[])]);
+        assert!(too_late.due(at(19, 8, 2, 0)).is_empty());
+    }
+}
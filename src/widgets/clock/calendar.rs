@@ -0,0 +1,222 @@
+use super::ics::{self, CalendarEvent};
+use crate::config::CalendarConfig;
+use crate::logger::Logger;
+use crate::paths;
+use crate::theme;
+use crate::widgets::{GJWidget, WidgetErrors};
+use chrono::{Datelike, Days, Months, NaiveDate, NaiveDateTime, Weekday};
+use chrono_tz::Tz;
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, Borders, Padding, Paragraph};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+const EVENT_RELOAD_INTERVAL: Duration = Duration::from_secs(300);
+
+pub struct CalendarWidget {
+    config: CalendarConfig,
+    week_start: Weekday,
+    events_path: Option<PathBuf>,
+    events: Vec<CalendarEvent>,
+    /// Zone event times are converted to, the clock's zone.
+    timezone: Option<Tz>,
+    loaded_at: Option<Instant>,
+    now: NaiveDateTime,
+    month_offset: i32,
+    errors: WidgetErrors,
+    logger: &'static Logger,
+}
+
+impl CalendarWidget {
+    pub fn new(config: CalendarConfig, timezone: Option<Tz>, logger: &'static Logger) -> Self {
+        let week_start = config.week_start.parse::<Weekday>().unwrap_or_else(|_| {
+            logger.error(&format!(
+                "Invalid calendar week_start '{}', using monday",
+                config.week_start
+            ));
+            Weekday::Mon
+        });
+        let events_path = config.events.as_deref().map(paths::expand_home);
+        Self {
+            config,
+            week_start,
+            events_path,
+            events: Vec::new(),
+            timezone,
+            loaded_at: None,
+            now: chrono::Local::now().naive_local(),
+            month_offset: 0,
+            errors: WidgetErrors::default(),
+            logger,
+        }
+    }
+
+    pub fn set_now(&mut self, now: NaiveDateTime) {
+        self.now = now;
+    }
+
+    pub fn shift_month(&mut self, delta: i32) {
+        self.month_offset += delta;
+    }
+
+    pub fn reset_month(&mut self) {
+        self.month_offset = 0;
+    }
+
+    fn shown_month(&self) -> NaiveDate {
+        let first = self.now.date().with_day(1).unwrap_or(self.now.date());
+        let months = Months::new(self.month_offset.unsigned_abs());
+        let shifted = if self.month_offset >= 0 {
+            first.checked_add_months(months)
+        } else {
+            first.checked_sub_months(months)
+        };
+        shifted.unwrap_or(first)
+    }
+
+    fn has_event(&self, date: NaiveDate) -> bool {
+        self.events.iter().any(|event| event.occurs_on(date))
+    }
+
+    fn grid_lines(&self) -> Vec<Line<'static>> {
+        let theme = theme::current();
+        let muted = Style::default().fg(theme.muted);
+        let month = self.shown_month();
+        let today = self.now.date();
+        let mut lines = vec![Line::styled(
+            month.format("%B %Y").to_string(),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )];
+
+        let mut header = Vec::new();
+        if self.config.week_numbers {
+            header.push(Span::styled("Wk ", muted));
+        }
+        let mut weekday = self.week_start;
+        for _ in 0..7 {
+            let name: String = weekday.to_string().chars().take(2).collect();
+            header.push(Span::styled(format!("{:>2} ", name), muted));
+            weekday = weekday.succ();
+        }
+        lines.push(Line::from(header));
+
+        let lead = month.weekday().days_since(self.week_start) as u64;
+        let mut row_start = month - Days::new(lead);
+        while row_start.month() == month.month() || row_start < month {
+            let mut spans = Vec::new();
+            if self.config.week_numbers {
+                // The ISO week is the one containing the row's Monday
+                let monday = row_start + Days::new(Weekday::Mon.days_since(self.week_start) as u64);
+                spans.push(Span::styled(
+                    format!("{:>2} ", monday.iso_week().week()),
+                    muted.add_modifier(Modifier::DIM),
+                ));
+            }
+            for offset in 0..7 {
+                let day = row_start + Days::new(offset);
+                let mut style = if day.month() == month.month() {
+                    Style::default().fg(theme.fg)
+                } else {
+                    muted.add_modifier(Modifier::DIM)
+                };
+                if self.has_event(day) {
+                    style = style
+                        .fg(theme.warning)
+                        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+                }
+                if day == today {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+                spans.push(Span::styled(format!("{:>2}", day.day()), style));
+                spans.push(Span::raw(" "));
+            }
+            lines.push(Line::from(spans));
+            row_start = row_start + Days::new(7);
+        }
+
+        lines
+    }
+
+    fn upcoming_lines(&self) -> Vec<Line<'static>> {
+        let theme = theme::current();
+        let today = self.now.date();
+        let mut upcoming: Vec<CalendarEvent> = self
+            .events
+            .iter()
+            .filter_map(|event| event.occurrence_on(today))
+            .filter(|event| event.all_day || event.end.unwrap_or(event.start) >= self.now)
+            .collect();
+        upcoming.sort_by_key(|event| (!event.all_day, event.start));
+
+        upcoming
+            .into_iter()
+            .map(|event| {
+                let when = if event.all_day || event.start.date() != today {
+                    "all day".to_string()
+                } else {
+                    event.start.format("%H:%M").to_string()
+                };
+                Line::from(vec![
+                    Span::styled(format!("{:>7} ", when), Style::default().fg(theme.warning)),
+                    Span::styled(event.summary.clone(), Style::default().fg(theme.fg)),
+                ])
+            })
+            .collect()
+    }
+}
+
+impl GJWidget for CalendarWidget {
+    fn error(&self) -> Option<String> {
+        self.errors.current()
+    }
+
+    fn poll(&mut self) {
+        let Some(path) = &self.events_path else {
+            return;
+        };
+        if self
+            .loaded_at
+            .is_some_and(|loaded| loaded.elapsed() < EVENT_RELOAD_INTERVAL)
+        {
+            return;
+        }
+        match ics::load_events(path, self.timezone) {
+            Ok(calendar) => {
+                if calendar.unsupported_rules > 0 {
+                    self.logger.debug(&format!(
+                        "{} recurring events use unsupported RRULEs, showing their first occurrence only",
+                        calendar.unsupported_rules
+                    ));
+                }
+                self.events = calendar.events;
+                self.errors.clear("load events");
+            }
+            Err(err) => {
+                self.logger.error(&err);
+                self.errors.set("load events", &err);
+            }
+        }
+        self.loaded_at = Some(Instant::now());
+    }
+
+    fn render(&self, f: &mut Frame, area: Rect) {
+        let mut text = Text::from(self.grid_lines());
+        let upcoming = self.upcoming_lines();
+        if !upcoming.is_empty() {
+            text.lines.push(Line::default());
+            text.lines.extend(upcoming);
+        }
+
+        let paragraph = Paragraph::new(text).alignment(Alignment::Center).block(
+            Block::default()
+                .borders(Borders::NONE)
+                .padding(Padding::top(1)),
+        );
+        f.render_widget(paragraph, area);
+    }
+}
@@ -0,0 +1,497 @@
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use std::fs;
+use std::path::Path;
+
+/// Upper bound on the days scanned when turning an RRULE `COUNT` into an end date.
+const MAX_COUNT_SCAN_DAYS: u64 = 366 * 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// The subset of RFC 5545 RRULE that is expanded: a frequency with
+/// `INTERVAL`, `UNTIL`, `COUNT` and `BYDAY` weekdays for daily and weekly
+/// rules, plus `EXDATE`s.
+#[derive(Debug, Clone)]
+struct Recurrence {
+    frequency: Frequency,
+    interval: u32,
+    by_day: Vec<Weekday>,
+    /// Last date an occurrence may start on, from `UNTIL` or `COUNT`.
+    until: Option<NaiveDate>,
+    exdates: Vec<NaiveDate>,
+}
+
+impl Recurrence {
+    /// Parses an RRULE value, `None` for rules outside the supported subset.
+    /// A UTC `UNTIL` is converted to `timezone` like the event's start.
+    fn parse(value: &str, first: NaiveDate, timezone: Option<Tz>) -> Option<Self> {
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut by_day = Vec::new();
+        let mut until = None;
+        let mut count = None;
+        for part in value.split(';') {
+            let (key, val) = part.split_once('=')?;
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match val.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return None,
+                    })
+                }
+                "INTERVAL" => interval = val.parse::<u32>().ok()?.max(1),
+                "UNTIL" => {
+                    until = Some(parse_date_time(val, std::iter::empty(), timezone)?.0.date())
+                }
+                "COUNT" => count = Some(val.parse::<usize>().ok()?),
+                // Plain weekdays only, ordinals like "2MO" are not supported
+                "BYDAY" => {
+                    for day in val.split(',') {
+                        by_day.push(parse_weekday(day)?);
+                    }
+                }
+                "WKST" => {}
+                _ => return None,
+            }
+        }
+        let frequency = frequency?;
+        if !by_day.is_empty() && !matches!(frequency, Frequency::Daily | Frequency::Weekly) {
+            return None;
+        }
+        if frequency == Frequency::Weekly && by_day.is_empty() {
+            by_day.push(first.weekday());
+        }
+
+        let mut recurrence = Self {
+            frequency,
+            interval,
+            by_day,
+            until,
+            exdates: Vec::new(),
+        };
+        if let Some(count) = count {
+            let last = (0..MAX_COUNT_SCAN_DAYS)
+                .filter_map(|offset| first.checked_add_days(Days::new(offset)))
+                .filter(|date| recurrence.matches(first, *date))
+                .take(count)
+                .last();
+            recurrence.until = match (recurrence.until, last) {
+                (Some(until), Some(last)) => Some(until.min(last)),
+                (until, last) => until.or(last),
+            };
+        }
+        Some(recurrence)
+    }
+
+    /// Whether an occurrence starts on `date`, ignoring EXDATEs.
+    fn matches(&self, first: NaiveDate, date: NaiveDate) -> bool {
+        if date < first || self.until.is_some_and(|until| date > until) {
+            return false;
+        }
+        let interval = self.interval as i64;
+        if !self.by_day.is_empty() && !self.by_day.contains(&date.weekday()) {
+            return false;
+        }
+        match self.frequency {
+            Frequency::Daily => (date - first).num_days() % interval == 0,
+            Frequency::Weekly => {
+                let week = |d: NaiveDate| d - Days::new(d.weekday().num_days_from_monday() as u64);
+                ((week(date) - week(first)).num_days() / 7) % interval == 0
+            }
+            Frequency::Monthly => {
+                let months = (date.year() - first.year()) as i64 * 12 + date.month() as i64
+                    - first.month() as i64;
+                date.day() == first.day() && months % interval == 0
+            }
+            Frequency::Yearly => {
+                date.month() == first.month()
+                    && date.day() == first.day()
+                    && (date.year() - first.year()) as i64 % interval == 0
+            }
+        }
+    }
+}
+
+fn parse_weekday(value: &str) -> Option<Weekday> {
+    match value.trim().to_ascii_uppercase().as_str() {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// A single VEVENT, with times converted to the clock's time zone.
+#[derive(Debug, Clone)]
+pub struct CalendarEvent {
+    pub summary: String,
+    pub start: NaiveDateTime,
+    pub end: Option<NaiveDateTime>,
+    pub all_day: bool,
+    recurrence: Option<Recurrence>,
+}
+
+impl CalendarEvent {
+    /// Days the event spans after its start date. All-day DTEND is exclusive.
+    fn extra_days(&self) -> u64 {
+        let first = self.start.date();
+        let last = match self.end {
+            Some(end) if self.all_day => end.date().pred_opt().unwrap_or(first),
+            Some(end) => end.date(),
+            None => first,
+        };
+        (last - first).num_days().max(0) as u64
+    }
+
+    /// The occurrence covering `date`, with start and end moved to it.
+    pub fn occurrence_on(&self, date: NaiveDate) -> Option<CalendarEvent> {
+        let first = self.start.date();
+        let Some(recurrence) = &self.recurrence else {
+            let last = first + Days::new(self.extra_days());
+            return (first <= date && date <= last).then(|| self.clone());
+        };
+        (0..=self.extra_days())
+            .filter_map(|offset| date.checked_sub_days(Days::new(offset)))
+            .find(|start| recurrence.matches(first, *start) && !recurrence.exdates.contains(start))
+            .map(|start| {
+                let shift = start - first;
+                CalendarEvent {
+                    start: self.start + shift,
+                    end: self.end.map(|end| end + shift),
+                    recurrence: None,
+                    ..self.clone()
+                }
+            })
+    }
+
+    pub fn occurs_on(&self, date: NaiveDate) -> bool {
+        self.occurrence_on(date).is_some()
+    }
+}
+
+/// Events read from a calendar, with the number of recurrence rules that
+/// could not be expanded (those events only show their first occurrence).
+#[derive(Debug, Default)]
+pub struct Calendar {
+    pub events: Vec<CalendarEvent>,
+    pub unsupported_rules: usize,
+}
+
+/// Reads events from a single `.ics` file or a vdir (directory of `.ics` files).
+/// Times are converted to `timezone`, the system zone when `None`.
+pub fn load_events(path: &Path, timezone: Option<Tz>) -> Result<Calendar, String> {
+    if path.is_dir() {
+        let mut calendar = Calendar::default();
+        let entries = fs::read_dir(path)
+            .map_err(|e| format!("Failed to read calendar dir {}: {}", path.display(), e))?;
+        for entry in entries.flatten() {
+            let entry_path = entry.path();
+            if entry_path.is_dir() || entry_path.extension().is_some_and(|ext| ext == "ics") {
+                let loaded = load_events(&entry_path, timezone)?;
+                calendar.events.extend(loaded.events);
+                calendar.unsupported_rules += loaded.unsupported_rules;
+            }
+        }
+        Ok(calendar)
+    } else {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read calendar {}: {}", path.display(), e))?;
+        Ok(parse_events(&content, timezone))
+    }
+}
+
+pub fn parse_events(content: &str, timezone: Option<Tz>) -> Calendar {
+    let mut calendar = Calendar::default();
+    let mut in_event = false;
+    let mut summary = String::new();
+    let mut start: Option<(NaiveDateTime, bool)> = None;
+    let mut end: Option<(NaiveDateTime, bool)> = None;
+    let mut rrule: Option<String> = None;
+    let mut exdates: Vec<NaiveDate> = Vec::new();
+
+    for line in unfold_lines(content) {
+        let Some((name_and_params, value)) = line.split_once(':') else {
+            continue;
+        };
+        let mut params = name_and_params.split(';');
+        let name = params.next().unwrap_or_default().to_ascii_uppercase();
+
+        match name.as_str() {
+            "BEGIN" if value.eq_ignore_ascii_case("VEVENT") => {
+                in_event = true;
+                summary.clear();
+                start = None;
+                end = None;
+                rrule = None;
+                exdates.clear();
+            }
+            "END" if value.eq_ignore_ascii_case("VEVENT") => {
+                in_event = false;
+                if let Some((start, all_day)) = start {
+                    let recurrence = rrule.as_deref().and_then(|rule| {
+                        let recurrence = Recurrence::parse(rule, start.date(), timezone);
+                        if recurrence.is_none() {
+                            calendar.unsupported_rules += 1;
+                        }
+                        recurrence
+                    });
+                    calendar.events.push(CalendarEvent {
+                        summary: unescape(&summary),
+                        start,
+                        end: end.map(|(end, _)| end),
+                        all_day,
+                        recurrence: recurrence.map(|recurrence| Recurrence {
+                            exdates: exdates.clone(),
+                            ..recurrence
+                        }),
+                    });
+                }
+            }
+            "SUMMARY" if in_event => summary = value.to_string(),
+            "DTSTART" if in_event => start = parse_date_time(value, params, timezone),
+            "DTEND" if in_event => end = parse_date_time(value, params, timezone),
+            "RRULE" if in_event => rrule = Some(value.to_string()),
+            "EXDATE" if in_event => {
+                let params: Vec<&str> = params.collect();
+                exdates.extend(value.split(',').filter_map(|date| {
+                    parse_date_time(date, params.iter().copied(), timezone)
+                        .map(|(date, _)| date.date())
+                }));
+            }
+            _ => {}
+        }
+    }
+
+    calendar
+}
+
+/// Joins RFC 5545 folded lines (continuations start with a space or tab).
+fn unfold_lines(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in content.lines() {
+        if let Some(continuation) = raw.strip_prefix([' ', '\t'])
+            && let Some(last) = lines.last_mut()
+        {
+            last.push_str(continuation);
+        } else {
+            lines.push(raw.trim_end_matches('\r').to_string());
+        }
+    }
+    lines
+}
+
+/// Wall-clock time of `utc` in `timezone`, the system zone when `None`.
+fn to_zone(utc: DateTime<Utc>, timezone: Option<Tz>) -> NaiveDateTime {
+    match timezone {
+        Some(tz) => utc.with_timezone(&tz).naive_local(),
+        None => utc.with_timezone(&Local).naive_local(),
+    }
+}
+
+/// Parses a DATE or DATE-TIME value. UTC and TZID times are converted to
+/// `timezone`, floating times are kept as they are.
+fn parse_date_time<'a>(
+    value: &str,
+    params: impl Iterator<Item = &'a str>,
+    timezone: Option<Tz>,
+) -> Option<(NaiveDateTime, bool)> {
+    let mut tzid = None;
+    let mut is_date = false;
+    for param in params {
+        match param.split_once('=') {
+            Some((key, val)) if key.eq_ignore_ascii_case("TZID") => tzid = Some(val),
+            Some((key, val)) if key.eq_ignore_ascii_case("VALUE") => {
+                is_date = val.eq_ignore_ascii_case("DATE")
+            }
+            _ => {}
+        }
+    }
+
+    let value = value.trim();
+    if is_date || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        return Some((date.and_hms_opt(0, 0, 0)?, true));
+    }
+
+    if let Some(utc) = value.strip_suffix('Z') {
+        let naive = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some((to_zone(Utc.from_utc_datetime(&naive), timezone), false));
+    }
+
+    let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    let zoned = match tzid.and_then(|name| name.trim_matches('"').parse::<Tz>().ok()) {
+        Some(tz) => tz
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|zoned| to_zone(zoned.with_timezone(&Utc), timezone))
+            .unwrap_or(naive),
+        None => naive,
+    };
+    Some((zoned, false))
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("\\n", " ")
+        .replace("\\N", " ")
+        .replace("\\,", ",")
+        .replace("\\;", ";")
+        .replace("\\\\", "\\")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    /// Wraps VEVENT lines in a calendar and parses it in `zone`.
+    fn parse(event: &str, zone: &str) -> Calendar {
+        let content = format!(
+            "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\n{}\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n",
+            event.trim().replace('\n', "\r\n")
+        );
+        parse_events(&content, Some(zone.parse().unwrap()))
+    }
+
+    /// Dates between `from` and `to` on which the single parsed event occurs.
+    fn occurrences(calendar: &Calendar, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+        assert_eq!(calendar.events.len(), 1);
+        from.iter_days()
+            .take_while(|day| *day <= to)
+            .filter(|day| {
+                calendar.events[0]
+                    .occurrence_on(*day)
+                    .is_some_and(|event| event.start.date() == *day)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn count_includes_excluded_dates() {
+        let calendar = parse(
+            "SUMMARY:Sync
+DTSTART;TZID=Europe/Helsinki:20260105T100000
+RRULE:FREQ=WEEKLY;BYDAY=MO,WE;COUNT=5
+EXDATE;TZID=Europe/Helsinki:20260107T100000",
+            "Europe/Helsinki",
+        );
+        assert_eq!(
+            occurrences(&calendar, date(2026, 1, 1), date(2026, 2, 28)),
+            vec![
+                date(2026, 1, 5),
+                date(2026, 1, 12),
+                date(2026, 1, 14),
+                date(2026, 1, 19)
+            ]
+        );
+    }
+
+    #[test]
+    fn weekly_interval_skips_weeks() {
+        let calendar = parse(
+            "SUMMARY:Review
+DTSTART:20260105T090000
+RRULE:FREQ=WEEKLY;INTERVAL=2;UNTIL=20260215T000000Z",
+            "UTC",
+        );
+        assert_eq!(
+            occurrences(&calendar, date(2026, 1, 1), date(2026, 3, 31)),
+            vec![date(2026, 1, 5), date(2026, 1, 19), date(2026, 2, 2)]
+        );
+    }
+
+    #[test]
+    fn monthly_on_the_31st_skips_shorter_months() {
+        let calendar = parse(
+            "SUMMARY:Invoice
+DTSTART;VALUE=DATE:20260131
+RRULE:FREQ=MONTHLY;COUNT=4",
+            "UTC",
+        );
+        assert_eq!(
+            occurrences(&calendar, date(2026, 1, 1), date(2026, 12, 31)),
+            vec![
+                date(2026, 1, 31),
+                date(2026, 3, 31),
+                date(2026, 5, 31),
+                date(2026, 7, 31)
+            ]
+        );
+    }
+
+    #[test]
+    fn all_day_end_is_exclusive() {
+        let calendar = parse(
+            "SUMMARY:Trip
+DTSTART;VALUE=DATE:20261019
+DTEND;VALUE=DATE:20261022",
+            "UTC",
+        );
+        let event = &calendar.events[0];
+        assert!(event.all_day);
+        assert!(!event.occurs_on(date(2026, 10, 18)));
+        assert!(event.occurs_on(date(2026, 10, 19)));
+        assert!(event.occurs_on(date(2026, 10, 21)));
+        assert!(!event.occurs_on(date(2026, 10, 22)));
+    }
+
+    #[test]
+    fn utc_start_moves_to_the_next_day_in_the_clock_zone() {
+        let calendar = parse(
+            "SUMMARY:Late call
+DTSTART:20261019T223000Z
+DTEND:20261019T233000Z
+RRULE:FREQ=DAILY;UNTIL=20261020T223000Z",
+            "Europe/Helsinki",
+        );
+        let event = &calendar.events[0];
+        assert_eq!(
+            event.start,
+            date(2026, 10, 20).and_hms_opt(1, 30, 0).unwrap()
+        );
+        assert!(!event.occurs_on(date(2026, 10, 19)));
+        // UNTIL is converted too, so the second occurrence lands on the 21st.
+        assert_eq!(
+            occurrences(&calendar, date(2026, 10, 18), date(2026, 10, 25)),
+            vec![date(2026, 10, 20), date(2026, 10, 21)]
+        );
+    }
+
+    #[test]
+    fn unfolds_continuation_lines() {
+        let calendar = parse(
+            "SUMMARY:Quarterly planning\n  and budget\nDTSTART;VALUE=DATE:20261019",
+            "UTC",
+        );
+        assert_eq!(calendar.events[0].summary, "Quarterly planning and budget");
+    }
+
+    #[test]
+    fn counts_unsupported_rules() {
+        let calendar = parse(
+            "SUMMARY:Board
+DTSTART;VALUE=DATE:20261012
+RRULE:FREQ=MONTHLY;BYDAY=2MO",
+            "UTC",
+        );
+        assert_eq!(calendar.unsupported_rules, 1);
+        assert!(calendar.events[0].occurs_on(date(2026, 10, 12)));
+        assert!(!calendar.events[0].occurs_on(date(2026, 11, 9)));
+    }
+}
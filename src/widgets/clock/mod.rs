@@ -0,0 +1,656 @@
+use crate::fontloader;
+use crate::fontloader::{FontChain, TextDecoration};
+use crate::logger::Logger;
+use crate::notify::notify;
+use crate::process::spawn_reaped;
+use crate::theme;
+use crate::widgets::{GJWidget, WidgetErrors};
+use crate::{config::ClockConfig, fontloader::load_font};
+use alarm::{Alarm, AlarmScheduler};
+use calendar::CalendarWidget;
+use chrono::{DateTime, Datelike, FixedOffset, Local, Utc};
+use chrono_tz::Tz;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Padding;
+use ratatui::{
+    layout::Alignment,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Paragraph},
+};
+use std::path::PathBuf;
+use std::process::Command;
+
+pub mod alarm;
+pub mod calendar;
+mod ics;
+pub mod timer;
+
+use timer::Timer;
+
+/// Where the clock reads the current time from. Everything time-dependent
+/// (rendering, timers, alarms) goes through this instead of `Local::now()`.
+pub trait ClockSource {
+    fn now(&self) -> DateTime<FixedOffset>;
+    /// Zone calendar events are shown in, the system zone when `None`.
+    fn timezone(&self) -> Option<Tz> {
+        None
+    }
+}
+
+/// Wall-clock time in the configured zone, or the system local zone.
+pub struct SystemClock {
+    timezone: Option<Tz>,
+}
+
+impl ClockSource for SystemClock {
+    fn now(&self) -> DateTime<FixedOffset> {
+        match self.timezone {
+            Some(tz) => Utc::now().with_timezone(&tz).fixed_offset(),
+            None => Local::now().fixed_offset(),
+        }
+    }
+
+    fn timezone(&self) -> Option<Tz> {
+        self.timezone
+    }
+}
+
+/// An alarm that went off and is flashing the clock.
+struct RingingAlarm {
+    alarm: Alarm,
+    until_ms: i64,
+}
+
+struct WorldClock {
+    label: String,
+    timezone: Tz,
+}
+
+pub struct ClockWidget {
+    pub config: ClockConfig,
+    font_time: Option<FontChain>,
+    font_date: Option<FontChain>,
+    time_decoration: TextDecoration,
+    date_decoration: TextDecoration,
+    source: Box<dyn ClockSource>,
+    world_clocks: Vec<WorldClock>,
+    calendar: CalendarWidget,
+    calendar_toggled: bool,
+    focused: bool,
+    timer: Option<Timer>,
+    timer_state: Option<PathBuf>,
+    alarms: AlarmScheduler,
+    ringing: Option<RingingAlarm>,
+    errors: WidgetErrors,
+    logger: &'static Logger,
+}
+
+impl ClockWidget {
+    pub fn new(config: ClockConfig, logger: &'static Logger) -> Self {
+        let timezone = config
+            .timezone
+            .as_deref()
+            .and_then(|name| parse_timezone(name, logger));
+        Self::with_source(
+            config,
+            Box::new(SystemClock { timezone }),
+            Some(timer::state_path()),
+            logger,
+        )
+    }
+
+    /// A clock reading the time from `source`. The timer is restored from and
+    /// saved to `timer_state`, or not persisted when it is `None`.
+    pub fn with_source(
+        config: ClockConfig,
+        source: Box<dyn ClockSource>,
+        timer_state: Option<PathBuf>,
+        logger: &'static Logger,
+    ) -> Self {
+        let font_time = load_font(&config.time_font, logger);
+        let font_date = load_font(&config.date_font, logger);
+        let world_clocks = config
+            .world_clock
+            .iter()
+            .filter_map(|entry| {
+                parse_timezone(&entry.timezone, logger).map(|timezone| WorldClock {
+                    label: entry.label.clone(),
+                    timezone,
+                })
+            })
+            .collect();
+        let calendar = CalendarWidget::new(config.calendar.clone(), source.timezone(), logger);
+        let alarms = config
+            .alarms
+            .iter()
+            .filter_map(|alarm| match Alarm::from_config(alarm) {
+                Ok(alarm) => Some(alarm),
+                Err(err) => {
+                    logger.warn(&format!("Skipping alarm: {}", err));
+                    None
+                }
+            })
+            .collect();
+        Self {
+            font_time,
+            font_date,
+            time_decoration: TextDecoration::new(
+                &config.time_gradient,
+                config.time_gradient_direction,
+                &config.time_colors,
+                config.time_effect,
+            ),
+            date_decoration: TextDecoration::new(
+                &config.date_gradient,
+                config.date_gradient_direction,
+                &config.date_colors,
+                config.date_effect,
+            ),
+            source,
+            world_clocks,
+            calendar,
+            calendar_toggled: false,
+            focused: false,
+            timer: timer_state.as_deref().and_then(timer::load_state),
+            timer_state,
+            alarms: AlarmScheduler::new(alarms),
+            ringing: None,
+            errors: WidgetErrors::default(),
+            logger,
+            config,
+        }
+    }
+
+    /// Shared by key bindings and the control socket (`clock <command> [arg]`).
+    fn run_timer_command(&mut self, command: &str, arg: Option<&str>) -> Result<String, String> {
+        let now_ms = self.now().timestamp_millis();
+        match command {
+            "timer" => {
+                let value = arg.unwrap_or(&self.config.timer.default_timer);
+                let duration = timer::parse_duration(value)
+                    .ok_or_else(|| format!("invalid duration '{}'", value))?;
+                self.timer = Some(Timer::countdown(duration, now_ms));
+            }
+            "stopwatch" => self.timer = Some(Timer::stopwatch(now_ms)),
+            "pomodoro" => self.timer = Some(Timer::pomodoro(&self.config.timer, now_ms)),
+            "pause" | "resume" | "toggle" | "reset" => {
+                let timer = self.timer.as_mut().ok_or("no timer running")?;
+                match command {
+                    "pause" => timer.pause(now_ms),
+                    "resume" => timer.resume(now_ms),
+                    "toggle" => timer.toggle(now_ms),
+                    _ => timer.reset(),
+                }
+            }
+            "stop" => self.timer = None,
+            "status" => {}
+            _ => return Err(format!("unknown clock command '{}'", command)),
+        }
+        self.save_timer();
+        Ok(self.timer_status(now_ms))
+    }
+
+    /// Handles `clock alarm list|add HH:MM [label...]|remove <n>|dismiss`.
+    fn run_alarm_command(&mut self, args: &[&str]) -> Result<String, String> {
+        match args {
+            [] | ["list"] => Ok(self
+                .alarms
+                .alarms
+                .iter()
+                .enumerate()
+                .map(|(i, alarm)| {
+                    let source = if alarm.runtime { " (runtime)" } else { "" };
+                    format!("{}: {}{}", i, alarm.describe(), source)
+                })
+                .collect::<Vec<_>>()
+                .join("\n")),
+            ["add", time, label @ ..] => {
+                let alarm = Alarm {
+                    time: alarm::parse_time(time)?,
+                    weekdays: Vec::new(),
+                    label: label.join(" "),
+                    command: None,
+                    notify: true,
+                    runtime: true,
+                };
+                let reply = format!("added {}", alarm.describe());
+                self.alarms.add(alarm);
+                Ok(reply)
+            }
+            ["remove", index] => {
+                let index: usize = index
+                    .parse()
+                    .map_err(|_| format!("invalid alarm index '{}'", index))?;
+                self.alarms
+                    .remove(index)
+                    .map(|alarm| format!("removed {}", alarm.describe()))
+                    .ok_or_else(|| format!("no alarm {}", index))
+            }
+            ["dismiss"] => {
+                self.ringing = None;
+                Ok("dismissed".to_string())
+            }
+            _ => Err(format!("unknown alarm command '{}'", args.join(" "))),
+        }
+    }
+
+    fn fire_alarm(&mut self, alarm: Alarm, now_ms: i64) {
+        let message = format!("Alarm {}", alarm.describe());
+        self.logger.info(&message);
+        if alarm.notify {
+            notify("gjpanel", &message, self.logger);
+        }
+        if let Some(command) = &alarm.command {
+            let logger = self.logger;
+            let label = alarm.label.clone();
+            let spawned = spawn_reaped(
+                Command::new("sh")
+                    .arg("-c")
+                    .arg(command)
+                    .env("GJPANEL_ALARM_LABEL", &alarm.label),
+                move |success| {
+                    if !success {
+                        logger.warn(&format!("Alarm command for '{}' failed", label));
+                    }
+                },
+            );
+            match spawned {
+                Ok(()) => self.errors.clear("alarm command"),
+                Err(err) => {
+                    let message = format!("Failed to run alarm command '{}': {}", command, err);
+                    self.logger.error(&message);
+                    self.errors.set("alarm command", &message);
+                }
+            }
+        }
+        self.ringing = Some(RingingAlarm {
+            alarm,
+            until_ms: now_ms + self.config.alarm_flash_secs as i64 * 1000,
+        });
+    }
+
+    fn alarm_lines(&self, now: &DateTime<FixedOffset>) -> Vec<Line<'static>> {
+        let theme = theme::current();
+        let today = now.date_naive().weekday();
+        self.alarms
+            .alarms
+            .iter()
+            .map(|alarm| {
+                let style = if alarm.repeats_on(today) {
+                    Style::default().fg(theme.fg)
+                } else {
+                    Style::default().fg(theme.muted).add_modifier(Modifier::DIM)
+                };
+                Line::styled(format!("\u{f0020} {}", alarm.describe()), style)
+            })
+            .collect()
+    }
+
+    fn timer_status(&self, now_ms: i64) -> String {
+        match &self.timer {
+            Some(timer) => format!(
+                "{} {}",
+                timer.display(now_ms),
+                timer.label(&self.config.timer)
+            ),
+            None => "no timer".to_string(),
+        }
+    }
+
+    fn save_timer(&mut self) {
+        let Some(path) = &self.timer_state else {
+            return;
+        };
+        match timer::save_state(path, &self.timer) {
+            Ok(()) => self.errors.clear("save timer"),
+            Err(err) => {
+                let message = format!("Failed to save timer state: {}", err);
+                self.logger.error(&message);
+                self.errors.set("save timer", &message);
+            }
+        }
+    }
+
+    fn calendar_visible(&self) -> bool {
+        self.calendar_toggled || (self.focused && self.config.calendar.show_on_focus)
+    }
+
+    fn now(&self) -> DateTime<FixedOffset> {
+        self.source.now()
+    }
+
+    fn world_clock_lines(&self, now: &DateTime<FixedOffset>) -> Vec<Line<'static>> {
+        let theme = theme::current();
+        let label_width = self
+            .world_clocks
+            .iter()
+            .map(|clock| clock.label.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        self.world_clocks
+            .iter()
+            .map(|clock| {
+                let zoned = now.with_timezone(&clock.timezone);
+                let day_offset = (zoned.date_naive() - now.date_naive()).num_days();
+                let marker = match day_offset {
+                    0 => "  ".to_string(),
+                    offset => format!("{:+}", offset),
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!("{:<width$} ", clock.label, width = label_width),
+                        Style::default().fg(theme.muted),
+                    ),
+                    Span::styled(
+                        zoned.format(&self.config.world_format).to_string(),
+                        Style::default().fg(theme.accent),
+                    ),
+                    Span::styled(
+                        format!(" {}", marker),
+                        Style::default().fg(theme.muted).add_modifier(Modifier::DIM),
+                    ),
+                ])
+            })
+            .collect()
+    }
+}
+
+fn parse_timezone(name: &str, logger: &Logger) -> Option<Tz> {
+    match name.parse::<Tz>() {
+        Ok(tz) => Some(tz),
+        Err(err) => {
+            logger.warn(&format!("Unknown timezone '{}': {}", name, err));
+            None
+        }
+    }
+}
+
+impl GJWidget for ClockWidget {
+    fn error(&self) -> Option<String> {
+        self.errors.current().or_else(|| self.calendar.error())
+    }
+
+    fn poll(&mut self) {
+        let now = self.now();
+        self.calendar.set_now(now.naive_local());
+        self.calendar.poll();
+
+        let now_ms = now.timestamp_millis();
+        for alarm in self.alarms.due(now.naive_local()) {
+            self.fire_alarm(alarm, now_ms);
+        }
+        if self
+            .ringing
+            .as_ref()
+            .is_some_and(|ringing| now_ms >= ringing.until_ms)
+        {
+            self.ringing = None;
+        }
+
+        let finished = self
+            .timer
+            .as_mut()
+            .and_then(|timer| timer.tick(now_ms, &self.config.timer));
+        if let Some(message) = finished {
+            self.logger.info(&message);
+            if self.config.timer.notify {
+                notify("gjpanel", &message, self.logger);
+            }
+            self.save_timer();
+        }
+    }
+
+    fn handle_command(&mut self, args: &[&str]) -> Option<String> {
+        match args {
+            ["clock", "alarm", rest @ ..] => Some(
+                self.run_alarm_command(rest)
+                    .unwrap_or_else(|err| format!("error: {}", err)),
+            ),
+            ["clock", command, rest @ ..] => Some(
+                self.run_timer_command(command, rest.first().copied())
+                    .unwrap_or_else(|err| format!("error: {}", err)),
+            ),
+            _ => None,
+        }
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> bool {
+        let timer_command = match key_event.code {
+            KeyCode::Char('t') => Some("timer"),
+            KeyCode::Char('s') => Some("stopwatch"),
+            KeyCode::Char('p') => Some("pomodoro"),
+            KeyCode::Char(' ') => Some("toggle"),
+            KeyCode::Char('r') => Some("reset"),
+            KeyCode::Char('x') => Some("stop"),
+            _ => None,
+        };
+        if let Some(command) = timer_command {
+            // Mistakes such as stopping without a timer are only worth a note.
+            if let Err(err) = self.run_timer_command(command, None) {
+                self.logger.info(&format!("Clock: {}", err));
+            }
+            return true;
+        }
+
+        match key_event.code {
+            KeyCode::Char('d') => self.ringing = None,
+            KeyCode::Enter | KeyCode::Char('c') => {
+                self.calendar_toggled = !self.calendar_visible();
+                self.calendar.reset_month();
+            }
+            KeyCode::Left | KeyCode::Char('h') if self.calendar_visible() => {
+                self.calendar.shift_month(-1)
+            }
+            KeyCode::Right | KeyCode::Char('l') if self.calendar_visible() => {
+                self.calendar.shift_month(1)
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn handle_click(&mut self) {
+        self.calendar_toggled = !self.calendar_toggled;
+        self.calendar.reset_month();
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn render(&self, f: &mut Frame, area: Rect) {
+        let theme = theme::current();
+        if self.calendar_visible() {
+            self.calendar.render(f, area);
+            return;
+        }
+
+        let time_style = Style {
+            fg: Some(theme.accent),
+            bg: Some(Color::default()),
+            underline_color: Some(Color::default()),
+            add_modifier: Modifier::empty(),
+            sub_modifier: Modifier::empty(),
+        };
+
+        let date_style = Style {
+            fg: Some(theme.accent),
+            bg: Some(Color::default()),
+            underline_color: Some(Color::default()),
+            add_modifier: Modifier::DIM | Modifier::BOLD,
+            sub_modifier: Modifier::empty(),
+        };
+        let now = self.now();
+        let time_str = now.format(&self.config.time_format).to_string();
+        let date_str = now.format(&self.config.date_format).to_string();
+
+        let flash_on = self.ringing.is_some() && now.timestamp() % 2 == 0;
+        let time_style = if flash_on {
+            time_style.add_modifier(Modifier::REVERSED)
+        } else {
+            time_style
+        };
+
+        let mut text = match &self.timer {
+            Some(timer) => {
+                let timer_str = timer.display(now.timestamp_millis());
+                let mut label_style = Style::default().fg(theme.muted);
+                if timer.finished {
+                    label_style = label_style
+                        .fg(theme.critical)
+                        .add_modifier(Modifier::SLOW_BLINK);
+                }
+                let mut text = fontloader::to_decorated_text(
+                    &self.font_time,
+                    &timer_str,
+                    time_style,
+                    area.width,
+                    &self.time_decoration,
+                );
+                text.lines
+                    .push(Line::styled(timer.label(&self.config.timer), label_style));
+                text + fontloader::to_decorated_text(
+                    &self.font_date,
+                    &time_str,
+                    date_style,
+                    area.width,
+                    &self.date_decoration,
+                )
+            }
+            None => {
+                fontloader::to_decorated_text(
+                    &self.font_time,
+                    &time_str,
+                    time_style,
+                    area.width,
+                    &self.time_decoration,
+                ) + fontloader::to_decorated_text(
+                    &self.font_date,
+                    &date_str,
+                    date_style,
+                    area.width,
+                    &self.date_decoration,
+                )
+            }
+        };
+
+        if let Some(ringing) = &self.ringing {
+            text.lines.push(Line::styled(
+                format!("\u{f0020} {}", ringing.alarm.describe()),
+                Style::default()
+                    .fg(theme.critical)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+
+        if !self.world_clocks.is_empty() {
+            text.lines.push(Line::default());
+            text.lines.extend(self.world_clock_lines(&now));
+        }
+
+        if !self.alarms.alarms.is_empty() {
+            text.lines.push(Line::default());
+            text.lines.extend(self.alarm_lines(&now));
+        }
+
+        let paragraph = Paragraph::new(text).alignment(Alignment::Center).block(
+            Block::default()
+                .borders(Borders::NONE)
+                .padding(Padding::top(5)),
+        );
+        f.render_widget(paragraph, area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logger::test_logger;
+    use chrono::{NaiveDate, TimeZone};
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// A clock whose time the test moves forward by hand.
+    struct FakeClock(Rc<Cell<DateTime<FixedOffset>>>);
+
+    impl ClockSource for FakeClock {
+        fn now(&self) -> DateTime<FixedOffset> {
+            self.0.get()
+        }
+    }
+
+    fn at(hour: u32, minute: u32, second: u32) -> DateTime<FixedOffset> {
+        let naive = NaiveDate::from_ymd_opt(2026, 10, 19)
+            .and_then(|date| date.and_hms_opt(hour, minute, second))
+            .unwrap();
+        FixedOffset::east_opt(2 * 3600)
+            .unwrap()
+            .from_local_datetime(&naive)
+            .unwrap()
+    }
+
+    fn config() -> ClockConfig {
+        toml::from_str(
+            r#"
+            time_format = "%H:%M"
+            date_format = "%d.%m.%Y"
+            time_font = ""
+            date_font = ""
+
+            [[alarms]]
+            time = "08:00"
+            label = "wake"
+            notify = false
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn alarm_rings_when_fake_clock_reaches_it() {
+        let now = Rc::new(Cell::new(at(7, 59, 50)));
+        let mut clock = ClockWidget::with_source(
+            config(),
+            Box::new(FakeClock(now.clone())),
+            None,
+            test_logger(),
+        );
+
+        clock.poll();
+        assert!(clock.ringing.is_none());
+
+        now.set(at(8, 0, 5));
+        clock.poll();
+        let ringing = clock.ringing.as_ref().expect("alarm should ring");
+        assert_eq!(ringing.alarm.label, "wake");
+        assert_eq!(clock.now(), at(8, 0, 5));
+    }
+
+    #[test]
+    fn timer_persists_to_the_given_state_path() {
+        let path = std::env::temp_dir().join(format!("gjpanel-timer-{}.toml", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let now = Rc::new(Cell::new(at(12, 0, 0)));
+        let mut clock = ClockWidget::with_source(
+            config(),
+            Box::new(FakeClock(now.clone())),
+            Some(path.clone()),
+            test_logger(),
+        );
+        assert!(clock.handle_command(&["clock", "timer", "5m"]).is_some());
+        assert!(path.is_file());
+
+        let restored = ClockWidget::with_source(
+            config(),
+            Box::new(FakeClock(now)),
+            Some(path.clone()),
+            test_logger(),
+        );
+        assert!(restored.timer.is_some());
+        let _ = std::fs::remove_file(&path);
+    }
+}
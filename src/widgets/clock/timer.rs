@@ -0,0 +1,269 @@
+use crate::config::TimerConfig;
+use crate::paths;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TimerKind {
+    Countdown,
+    Stopwatch,
+    Pomodoro,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PomodoroPhase {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+/// Countdown, stopwatch or pomodoro state. Times are wall-clock unix
+/// milliseconds so a running timer keeps counting while the panel is closed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Timer {
+    pub kind: TimerKind,
+    /// Target length in milliseconds, unused by the stopwatch.
+    pub duration_ms: u64,
+    /// Time accumulated before the current run segment.
+    pub elapsed_ms: u64,
+    /// Start of the current run segment, `None` while paused.
+    pub running_since: Option<i64>,
+    pub phase: PomodoroPhase,
+    pub completed_sessions: u32,
+    pub finished: bool,
+}
+
+impl Timer {
+    pub fn countdown(duration_ms: u64, now_ms: i64) -> Self {
+        Self::started(TimerKind::Countdown, duration_ms, now_ms)
+    }
+
+    pub fn stopwatch(now_ms: i64) -> Self {
+        Self::started(TimerKind::Stopwatch, 0, now_ms)
+    }
+
+    pub fn pomodoro(config: &TimerConfig, now_ms: i64) -> Self {
+        Self::started(
+            TimerKind::Pomodoro,
+            phase_duration(PomodoroPhase::Work, config),
+            now_ms,
+        )
+    }
+
+    fn started(kind: TimerKind, duration_ms: u64, now_ms: i64) -> Self {
+        Self {
+            kind,
+            duration_ms,
+            elapsed_ms: 0,
+            running_since: Some(now_ms),
+            phase: PomodoroPhase::Work,
+            completed_sessions: 0,
+            finished: false,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running_since.is_some()
+    }
+
+    pub fn elapsed(&self, now_ms: i64) -> u64 {
+        let running = self
+            .running_since
+            .map(|since| (now_ms - since).max(0) as u64)
+            .unwrap_or(0);
+        self.elapsed_ms + running
+    }
+
+    pub fn pause(&mut self, now_ms: i64) {
+        if self.running_since.is_some() {
+            self.elapsed_ms = self.elapsed(now_ms);
+            self.running_since = None;
+        }
+    }
+
+    pub fn resume(&mut self, now_ms: i64) {
+        if self.running_since.is_none() && !self.finished {
+            self.running_since = Some(now_ms);
+        }
+    }
+
+    pub fn toggle(&mut self, now_ms: i64) {
+        if self.is_running() {
+            self.pause(now_ms);
+        } else {
+            self.resume(now_ms);
+        }
+    }
+
+    /// Restarts the current timer (or pomodoro phase) from zero, paused.
+    pub fn reset(&mut self) {
+        self.elapsed_ms = 0;
+        self.running_since = None;
+        self.finished = false;
+    }
+
+    /// Advances completed countdowns and pomodoro phases. Returns a
+    /// notification message when something finished.
+    pub fn tick(&mut self, now_ms: i64, config: &TimerConfig) -> Option<String> {
+        if self.kind == TimerKind::Stopwatch
+            || self.finished
+            || self.elapsed(now_ms) < self.duration_ms
+        {
+            return None;
+        }
+
+        match self.kind {
+            TimerKind::Pomodoro => {
+                let done = self.phase;
+                if done == PomodoroPhase::Work {
+                    self.completed_sessions += 1;
+                }
+                self.phase = match done {
+                    PomodoroPhase::Work
+                        if config.pomodoro_sessions > 0
+                            && self
+                                .completed_sessions
+                                .is_multiple_of(config.pomodoro_sessions) =>
+                    {
+                        PomodoroPhase::LongBreak
+                    }
+                    PomodoroPhase::Work => PomodoroPhase::ShortBreak,
+                    _ => PomodoroPhase::Work,
+                };
+                self.duration_ms = phase_duration(self.phase, config);
+                self.elapsed_ms = 0;
+                self.running_since = Some(now_ms);
+                Some(format!(
+                    "{} finished, {} started",
+                    phase_name(done),
+                    phase_name(self.phase)
+                ))
+            }
+            _ => {
+                self.elapsed_ms = self.duration_ms;
+                self.running_since = None;
+                self.finished = true;
+                Some(format!(
+                    "Timer {} finished",
+                    format_duration(self.duration_ms)
+                ))
+            }
+        }
+    }
+
+    /// Remaining time for countdowns, elapsed time for the stopwatch.
+    pub fn display(&self, now_ms: i64) -> String {
+        match self.kind {
+            TimerKind::Stopwatch => format_duration(self.elapsed(now_ms)),
+            _ => format_duration(self.duration_ms.saturating_sub(self.elapsed(now_ms))),
+        }
+    }
+
+    pub fn label(&self, config: &TimerConfig) -> String {
+        let state = if self.finished {
+            "done"
+        } else if self.is_running() {
+            "running"
+        } else {
+            "paused"
+        };
+        match self.kind {
+            TimerKind::Countdown => format!("timer {}", state),
+            TimerKind::Stopwatch => format!("stopwatch {}", state),
+            TimerKind::Pomodoro => format!(
+                "{} {}/{} {}",
+                phase_name(self.phase),
+                self.completed_sessions % config.pomodoro_sessions.max(1) + 1,
+                config.pomodoro_sessions.max(1),
+                state
+            ),
+        }
+    }
+}
+
+fn phase_duration(phase: PomodoroPhase, config: &TimerConfig) -> u64 {
+    let value = match phase {
+        PomodoroPhase::Work => &config.pomodoro_work,
+        PomodoroPhase::ShortBreak => &config.pomodoro_break,
+        PomodoroPhase::LongBreak => &config.pomodoro_long_break,
+    };
+    parse_duration(value).unwrap_or(25 * 60 * 1000)
+}
+
+fn phase_name(phase: PomodoroPhase) -> &'static str {
+    match phase {
+        PomodoroPhase::Work => "work",
+        PomodoroPhase::ShortBreak => "break",
+        PomodoroPhase::LongBreak => "long break",
+    }
+}
+
+/// Parses "90", "90s", "25m", "1h30m" or "mm:ss" into milliseconds.
+pub fn parse_duration(value: &str) -> Option<u64> {
+    let value = value.trim();
+    if let Some((minutes, seconds)) = value.split_once(':') {
+        let minutes: u64 = minutes.parse().ok()?;
+        let seconds: u64 = seconds.parse().ok()?;
+        return Some((minutes * 60 + seconds) * 1000);
+    }
+
+    let mut total = 0;
+    let mut number = String::new();
+    for c in value.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let n: u64 = number.parse().ok()?;
+        number.clear();
+        total += match c {
+            'h' => n * 3600,
+            'm' => n * 60,
+            's' => n,
+            _ => return None,
+        };
+    }
+    if !number.is_empty() {
+        total += number.parse::<u64>().ok()?;
+    }
+    (total > 0).then_some(total * 1000)
+}
+
+pub fn format_duration(ms: u64) -> String {
+    let secs = ms.div_ceil(1000);
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersistedTimer {
+    timer: Option<Timer>,
+}
+
+/// Where the running timer is kept across panel restarts.
+pub fn state_path() -> PathBuf {
+    paths::state_dir().join("timer.toml")
+}
+
+pub fn load_state(path: &Path) -> Option<Timer> {
+    let content = fs::read_to_string(path).ok()?;
+    toml::from_str::<PersistedTimer>(&content).ok()?.timer
+}
+
+pub fn save_state(path: &Path, timer: &Option<Timer>) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let content = toml::to_string(&PersistedTimer {
+        timer: timer.clone(),
+    })
+    .map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| format!("{}: {}", path.display(), e))
+}
@@ -1,10 +1,49 @@
+use crossterm::event::KeyEvent;
 use ratatui::{Frame, layout::Rect};
 
 pub mod clock;
+pub mod network;
 pub mod sysinfo;
 pub mod weather;
 pub mod workspaces;
 pub trait GJWidget {
     fn poll(&mut self) {}
     fn render(&self, frame: &mut Frame, area: Rect);
+    /// Called with keys while the widget has focus. Returns true if the key was consumed.
+    fn handle_key_event(&mut self, _key_event: KeyEvent) -> bool {
+        false
+    }
+    fn handle_click(&mut self) {}
+    /// Called with control socket commands. Returns a reply if the widget handled it.
+    fn handle_command(&mut self, _args: &[&str]) -> Option<String> {
+        None
+    }
+    fn set_focused(&mut self, _focused: bool) {}
+    /// The widget's current error, rendered as a badge in its corner.
+    fn error(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Failures of a widget's operations ("load events", "save timer"), each set
+/// when its operation fails and cleared when that operation next succeeds.
+#[derive(Debug, Default)]
+pub struct WidgetErrors {
+    errors: Vec<(&'static str, String)>,
+}
+
+impl WidgetErrors {
+    pub fn set(&mut self, operation: &'static str, message: &str) {
+        self.clear(operation);
+        self.errors.push((operation, message.to_string()));
+    }
+
+    pub fn clear(&mut self, operation: &'static str) {
+        self.errors.retain(|(failed, _)| *failed != operation);
+    }
+
+    /// The most recent failure of any operation still failing.
+    pub fn current(&self) -> Option<String> {
+        self.errors.last().map(|(_, message)| message.clone())
+    }
 }
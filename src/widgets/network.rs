@@ -0,0 +1,352 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Sparkline},
+};
+use std::collections::HashMap;
+use std::fs;
+use std::process::Command;
+use std::time::{Duration, Instant};
+use sysinfo::Networks;
+
+use super::GJWidget;
+use crate::config::NetworkConfig;
+use crate::format::format_bytes;
+use crate::glob::glob_match;
+use crate::history::History;
+use crate::logger::Logger;
+use crate::theme;
+
+/// `iw` is a process spawn per wireless interface, so the SSID is not
+/// looked up on every poll.
+const SSID_REFRESH: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+struct Wireless {
+    ssid: Option<String>,
+    /// Signal level in dBm as reported by `/proc/net/wireless`.
+    signal: i32,
+}
+
+struct Interface {
+    name: String,
+    addresses: Vec<String>,
+    /// Bytes per second since the previous poll.
+    down_rate: u64,
+    up_rate: u64,
+    down_history: History,
+    up_history: History,
+    wireless: Option<Wireless>,
+}
+
+/// Interface names carrying a default route, from `/proc/net/route` and
+/// `/proc/net/ipv6_route`.
+fn default_route_interfaces() -> Vec<String> {
+    let read = |path: &str| fs::read_to_string(path).unwrap_or_default();
+    parse_default_routes(&read("/proc/net/route"), &read("/proc/net/ipv6_route"))
+}
+
+fn parse_default_routes(route: &str, ipv6_route: &str) -> Vec<String> {
+    let mut interfaces = Vec::new();
+    for line in route.lines().skip(1) {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.get(1) == Some(&"00000000") && fields.get(7) == Some(&"00000000") {
+            interfaces.push(fields[0].to_string());
+        }
+    }
+    for line in ipv6_route.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let Some(name) = fields.last() else {
+            continue;
+        };
+        if fields.len() == 10
+            && fields[0].chars().all(|c| c == '0')
+            && fields[1] == "00"
+            && *name != "lo"
+            && !interfaces.iter().any(|i| i == name)
+        {
+            interfaces.push(name.to_string());
+        }
+    }
+    interfaces
+}
+
+fn read_wireless_signals() -> HashMap<String, i32> {
+    fs::read_to_string("/proc/net/wireless")
+        .map(|content| parse_wireless_signals(&content))
+        .unwrap_or_default()
+}
+
+/// Signal level per wireless interface from `/proc/net/wireless`.
+fn parse_wireless_signals(content: &str) -> HashMap<String, i32> {
+    content
+        .lines()
+        .skip(2)
+        .filter_map(|line| {
+            let (name, rest) = line.split_once(':')?;
+            let level = rest.split_whitespace().nth(2)?;
+            let level: f32 = level.trim_end_matches('.').parse().ok()?;
+            Some((name.trim().to_string(), level as i32))
+        })
+        .collect()
+}
+
+fn read_ssid(interface: &str) -> Option<String> {
+    let output = Command::new("iw")
+        .args(["dev", interface, "link"])
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("SSID: ").map(str::to_string))
+}
+
+fn format_rate(rate: u64) -> String {
+    format!("{}/s", format_bytes(rate))
+}
+
+pub struct NetworkWidget {
+    networks: Networks,
+    filters: Vec<String>,
+    show_addresses: bool,
+    history_length: usize,
+    interfaces: Vec<Interface>,
+    default_routes: Vec<String>,
+    ssids: HashMap<String, Option<String>>,
+    ssids_checked: Option<Instant>,
+    previous_at: Instant,
+    logger: &'static Logger,
+}
+
+impl NetworkWidget {
+    pub fn new(config: NetworkConfig, logger: &'static Logger) -> Self {
+        Self {
+            networks: Networks::new_with_refreshed_list(),
+            filters: config.interfaces,
+            show_addresses: config.show_addresses,
+            history_length: config.history_length,
+            interfaces: Vec::new(),
+            default_routes: Vec::new(),
+            ssids: HashMap::new(),
+            ssids_checked: None,
+            previous_at: Instant::now(),
+            logger,
+        }
+    }
+
+    /// Configured globs ("wlan*", "enp3s0"), or everything but loopback.
+    fn is_shown(&self, name: &str) -> bool {
+        if self.filters.is_empty() {
+            name != "lo"
+        } else {
+            self.filters.iter().any(|pattern| glob_match(pattern, name))
+        }
+    }
+
+    fn refresh_ssids(&mut self, signals: &HashMap<String, i32>) {
+        if self
+            .ssids_checked
+            .is_some_and(|checked| checked.elapsed() < SSID_REFRESH)
+        {
+            return;
+        }
+        self.ssids = signals
+            .keys()
+            .map(|name| (name.clone(), read_ssid(name)))
+            .collect();
+        self.ssids_checked = Some(Instant::now());
+    }
+}
+
+impl GJWidget for NetworkWidget {
+    fn poll(&mut self) {
+        self.networks.refresh(true);
+        let elapsed = self.previous_at.elapsed().as_secs_f64().max(0.001);
+        self.previous_at = Instant::now();
+        self.default_routes = default_route_interfaces();
+        let signals = read_wireless_signals();
+        self.refresh_ssids(&signals);
+
+        let mut names: Vec<&String> = self
+            .networks
+            .list()
+            .keys()
+            .filter(|name| self.is_shown(name))
+            .collect();
+        names.sort();
+
+        let mut previous: HashMap<String, Interface> = self
+            .interfaces
+            .drain(..)
+            .map(|interface| (interface.name.clone(), interface))
+            .collect();
+        let mut interfaces = Vec::new();
+        for name in names {
+            let data = &self.networks.list()[name];
+            let mut interface = previous.remove(name).unwrap_or_else(|| {
+                self.logger
+                    .info(&format!("Network interface {} appeared", name));
+                Interface {
+                    name: name.clone(),
+                    addresses: Vec::new(),
+                    down_rate: 0,
+                    up_rate: 0,
+                    down_history: History::new(self.history_length),
+                    up_history: History::new(self.history_length),
+                    wireless: None,
+                }
+            });
+            interface.down_rate = (data.received() as f64 / elapsed) as u64;
+            interface.up_rate = (data.transmitted() as f64 / elapsed) as u64;
+            interface.down_history.push(interface.down_rate);
+            interface.up_history.push(interface.up_rate);
+            interface.addresses = data
+                .ip_networks()
+                .iter()
+                .map(|network| format!("{}/{}", network.addr, network.prefix))
+                .collect();
+            interface.wireless = signals.get(name).map(|signal| Wireless {
+                ssid: self.ssids.get(name).cloned().flatten(),
+                signal: *signal,
+            });
+            interfaces.push(interface);
+        }
+        self.interfaces = interfaces;
+    }
+
+    fn render(&self, f: &mut Frame, area: Rect) {
+        let theme = theme::current();
+        if self.interfaces.is_empty() {
+            f.render_widget(
+                Paragraph::new("no network interfaces").style(Style::new().fg(theme.muted)),
+                area,
+            );
+            return;
+        }
+
+        let heights: Vec<u16> = self
+            .interfaces
+            .iter()
+            .map(|interface| {
+                let addresses = if self.show_addresses {
+                    interface.addresses.len()
+                } else {
+                    0
+                };
+                addresses as u16 + 3
+            })
+            .collect();
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(heights.iter().map(|h| Constraint::Length(*h)))
+            .split(area);
+
+        for (interface, row) in self.interfaces.iter().zip(rows.iter()) {
+            let mut title = vec![Span::styled(
+                interface.name.clone(),
+                Style::new().fg(theme.fg).bold(),
+            )];
+            if self.default_routes.contains(&interface.name) {
+                title.push(Span::styled(" default", Style::new().fg(theme.accent)));
+            }
+            if let Some(wireless) = &interface.wireless {
+                if let Some(ssid) = &wireless.ssid {
+                    title.push(Span::styled(
+                        format!(" {}", ssid),
+                        Style::new().fg(theme.accent),
+                    ));
+                }
+                title.push(Span::styled(
+                    format!(" {}dBm", wireless.signal),
+                    Style::new().fg(theme.muted),
+                ));
+            }
+
+            let mut lines = vec![Line::from(title)];
+            if self.show_addresses {
+                lines.extend(
+                    interface
+                        .addresses
+                        .iter()
+                        .map(|address| Line::styled(address.clone(), Style::new().fg(theme.muted))),
+                );
+            }
+
+            let parts = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(lines.len() as u16),
+                    Constraint::Length(1),
+                    Constraint::Length(1),
+                ])
+                .split(*row);
+            f.render_widget(Paragraph::new(lines), parts[0]);
+
+            for (label, rate, history, part) in [
+                ("↓", interface.down_rate, &interface.down_history, parts[1]),
+                ("↑", interface.up_rate, &interface.up_history, parts[2]),
+            ] {
+                let text = format!("{} {:>9} ", label, format_rate(rate));
+                let columns = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([
+                        Constraint::Length(text.chars().count() as u16),
+                        Constraint::Min(0),
+                    ])
+                    .split(part);
+                f.render_widget(
+                    Paragraph::new(text).style(Style::new().fg(theme.muted)),
+                    columns[0],
+                );
+                let sparkline = Sparkline::default()
+                    .block(Block::default().borders(Borders::NONE))
+                    .data(history.tail(columns[1].width as usize))
+                    .max(history.peak().max(1))
+                    .style(Style::new().fg(theme.muted).bg(theme.background));
+                f.render_widget(sparkline, columns[1]);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_default_route_interfaces() {
+        let route = "\
+Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT
+wlan0\t00000000\t0100A8C0\t0003\t0\t0\t600\t00000000\t0\t0\t0
+wlan0\t0000A8C0\t00000000\t0001\t0\t0\t600\t00FFFFFF\t0\t0\t0
+docker0\t000011AC\t00000000\t0001\t0\t0\t0\t0000FFFF\t0\t0\t0
+";
+        let ipv6_route = "\
+00000000000000000000000000000000 00 00000000000000000000000000000000 00 fe800000000000000000000000000001 00000400 00000001 00000000 00000003 wlan0
+00000000000000000000000000000000 00 00000000000000000000000000000000 00 fe800000000000000000000000000001 00000400 00000001 00000000 00000003 eth0
+00000000000000000000000000000000 00 00000000000000000000000000000000 00 00000000000000000000000000000000 ffffffff 00000001 00000000 00200200 lo
+fe800000000000000000000000000000 40 00000000000000000000000000000000 00 00000000000000000000000000000000 00000100 00000001 00000000 00000001 wlan0
+";
+        assert_eq!(
+            parse_default_routes(route, ipv6_route),
+            vec!["wlan0", "eth0"]
+        );
+        assert!(parse_default_routes("", "").is_empty());
+    }
+
+    #[test]
+    fn parses_wireless_signals() {
+        let content = "\
+Inter-| sta-|   Quality        |   Discarded packets               | Missed | WE
+ face | tus | link level noise |  nwid  crypt   frag  retry   misc | beacon | 22
+wlan0: 0000   58.  -52.  -256        0      0      0      0     41        0
+ wlp3s0: 0000   30  -80  -256        0      0      0      0      0        0
+";
+        let signals = parse_wireless_signals(content);
+        assert_eq!(signals.len(), 2);
+        assert_eq!(signals["wlan0"], -52);
+        assert_eq!(signals["wlp3s0"], -80);
+    }
+}
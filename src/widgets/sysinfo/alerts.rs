@@ -0,0 +1,220 @@
+use crate::color::parse_color;
+use crate::config::{SysInfoConfig, ThresholdConfig};
+use crate::logger::Logger;
+use crate::notify::notify;
+use crate::process::spawn_reaped;
+use crate::theme;
+use ratatui::style::{Color, Modifier, Style};
+use std::collections::HashMap;
+use std::process::Command;
+
+use super::Metric;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Normal,
+    Warning,
+    Critical,
+}
+
+impl Level {
+    fn name(self) -> &'static str {
+        match self {
+            Level::Normal => "normal",
+            Level::Warning => "warning",
+            Level::Critical => "critical",
+        }
+    }
+}
+
+/// Level for `value` given the level it is currently at. A level is entered at
+/// its threshold but only left once the value drops `hysteresis` below it.
+pub fn level_for(threshold: &ThresholdConfig, value: u64, current: Level) -> Level {
+    let reached = |limit: Option<u64>, level: Level| {
+        limit.is_some_and(|limit| {
+            if current >= level {
+                value >= limit.saturating_sub(threshold.hysteresis)
+            } else {
+                value >= limit
+            }
+        })
+    };
+    if reached(threshold.critical, Level::Critical) {
+        Level::Critical
+    } else if reached(threshold.warning, Level::Warning) {
+        Level::Warning
+    } else {
+        Level::Normal
+    }
+}
+
+/// Warning and critical levels per (metric, device), with the actions fired
+/// when a level is entered.
+pub struct Alerts {
+    thresholds: HashMap<Metric, ThresholdConfig>,
+    levels: HashMap<(Metric, usize), Level>,
+    warning_color: Color,
+    critical_color: Color,
+}
+
+impl Alerts {
+    pub fn new(config: &SysInfoConfig, logger: &Logger) -> Self {
+        let theme = theme::current();
+        let mut thresholds = HashMap::new();
+        for (key, threshold) in &config.thresholds {
+            match Metric::from_key(key) {
+                Some(metric) => {
+                    thresholds.insert(metric, threshold.clone());
+                }
+                None => logger.warn(&format!("Unknown sysinfo threshold metric '{}'", key)),
+            }
+        }
+        let color = |value: &Option<String>, fallback: Color| match value {
+            Some(value) => parse_color(value).unwrap_or_else(|| {
+                logger.warn(&format!("Invalid threshold color '{}'", value));
+                fallback
+            }),
+            None => fallback,
+        };
+        Self {
+            thresholds,
+            levels: HashMap::new(),
+            warning_color: color(&config.warning_color, theme.warning),
+            critical_color: color(&config.critical_color, theme.critical),
+        }
+    }
+
+    pub fn level(&self, metric: Metric, device: usize) -> Level {
+        self.levels
+            .get(&(metric, device))
+            .copied()
+            .unwrap_or(Level::Normal)
+    }
+
+    /// Records a new sample, notifying or running the command on upward crossings.
+    pub fn update(
+        &mut self,
+        metric: Metric,
+        device: usize,
+        label: &str,
+        value: u64,
+        logger: &'static Logger,
+    ) {
+        let Some(threshold) = self.thresholds.get(&metric) else {
+            return;
+        };
+        let previous = self.level(metric, device);
+        let level = level_for(threshold, value, previous);
+        self.levels.insert((metric, device), level);
+        if level <= previous {
+            return;
+        }
+
+        let message = format!("{} {} {} ({})", label, value, metric.unit(), level.name());
+        logger.info(&message);
+        if threshold.notify {
+            notify("gjpanel", &message, logger);
+        }
+        if let Some(command) = &threshold.command
+            && let Err(err) = spawn_reaped(
+                Command::new("sh")
+                    .arg("-c")
+                    .arg(command)
+                    .env("GJPANEL_METRIC", metric.key())
+                    .env("GJPANEL_VALUE", value.to_string())
+                    .env("GJPANEL_LEVEL", level.name()),
+                |_| {},
+            )
+        {
+            logger.error(&format!(
+                "Failed to run threshold command '{}': {}",
+                command, err
+            ));
+        }
+    }
+
+    fn color(&self, metric: Metric, device: usize) -> Option<Color> {
+        match self.level(metric, device) {
+            Level::Normal => None,
+            Level::Warning => Some(self.warning_color),
+            Level::Critical => Some(self.critical_color),
+        }
+    }
+
+    /// Bar or sparkline style for the current level, `base` when normal.
+    pub fn style(&self, metric: Metric, device: usize, base: Style) -> Style {
+        let Some(color) = self.color(metric, device) else {
+            return base;
+        };
+        let blink = self
+            .thresholds
+            .get(&metric)
+            .is_some_and(|threshold| threshold.blink);
+        if blink && self.level(metric, device) == Level::Critical {
+            base.fg(color).add_modifier(Modifier::SLOW_BLINK)
+        } else {
+            base.fg(color)
+        }
+    }
+
+    /// Style for the value text drawn over the bar.
+    pub fn value_style(&self, metric: Metric, device: usize, base: Style) -> Style {
+        match self.color(metric, device) {
+            Some(color) => base.fg(theme::current().background).bg(color),
+            None => base,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn threshold() -> ThresholdConfig {
+        ThresholdConfig {
+            warning: Some(70),
+            critical: Some(90),
+            hysteresis: 5,
+            ..ThresholdConfig::default()
+        }
+    }
+
+    #[test]
+    fn raises_at_each_threshold() {
+        let threshold = threshold();
+        assert_eq!(level_for(&threshold, 69, Level::Normal), Level::Normal);
+        assert_eq!(level_for(&threshold, 70, Level::Normal), Level::Warning);
+        assert_eq!(level_for(&threshold, 90, Level::Normal), Level::Critical);
+        assert_eq!(level_for(&threshold, 90, Level::Warning), Level::Critical);
+    }
+
+    #[test]
+    fn holds_within_the_hysteresis_band() {
+        let threshold = threshold();
+        assert_eq!(level_for(&threshold, 66, Level::Warning), Level::Warning);
+        assert_eq!(level_for(&threshold, 65, Level::Warning), Level::Warning);
+        assert_eq!(level_for(&threshold, 86, Level::Critical), Level::Critical);
+        // Entering still needs the threshold itself.
+        assert_eq!(level_for(&threshold, 66, Level::Normal), Level::Normal);
+        assert_eq!(level_for(&threshold, 86, Level::Warning), Level::Warning);
+    }
+
+    #[test]
+    fn clears_below_the_hysteresis_band() {
+        let threshold = threshold();
+        assert_eq!(level_for(&threshold, 64, Level::Warning), Level::Normal);
+        assert_eq!(level_for(&threshold, 84, Level::Critical), Level::Warning);
+        assert_eq!(level_for(&threshold, 10, Level::Critical), Level::Normal);
+    }
+
+    #[test]
+    fn ignores_unset_thresholds() {
+        let threshold = ThresholdConfig {
+            critical: Some(90),
+            ..ThresholdConfig::default()
+        };
+        assert_eq!(level_for(&threshold, 80, Level::Normal), Level::Normal);
+        assert_eq!(level_for(&threshold, 95, Level::Normal), Level::Critical);
+        assert_eq!(level_for(&threshold, 87, Level::Critical), Level::Critical);
+    }
+}
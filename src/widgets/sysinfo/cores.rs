@@ -0,0 +1,132 @@
+use crate::color::Gradient;
+use crate::config::CoreView;
+use crate::theme;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Bar, BarChart, BarGroup, Block, Borders, Paragraph},
+};
+use std::fs;
+use sysinfo::Cpu;
+
+const HEATMAP_COLUMNS: usize = 8;
+
+#[derive(Debug, Clone)]
+pub struct CoreInfo {
+    pub usage: u64,
+    /// Current frequency in MHz.
+    pub frequency: u64,
+}
+
+impl CoreInfo {
+    pub fn from_cpu(cpu: &Cpu) -> Self {
+        Self {
+            usage: cpu.cpu_usage() as u64,
+            frequency: cpu.frequency(),
+        }
+    }
+}
+
+/// Distinct cpufreq scaling governors in use, e.g. "schedutil".
+pub fn read_governors(core_count: usize) -> Vec<String> {
+    let mut governors: Vec<String> = Vec::new();
+    for core in 0..core_count {
+        let path = format!(
+            "/sys/devices/system/cpu/cpu{}/cpufreq/scaling_governor",
+            core
+        );
+        if let Ok(governor) = fs::read_to_string(path) {
+            let governor = governor.trim().to_string();
+            if !governors.contains(&governor) {
+                governors.push(governor);
+            }
+        }
+    }
+    governors
+}
+
+fn format_ghz(mhz: u64) -> String {
+    format!("{:.1}G", mhz as f64 / 1000.0)
+}
+
+fn usage_gradient() -> Gradient {
+    let theme = theme::current();
+    Gradient::new(vec![
+        theme.border,
+        theme.accent,
+        theme.warning,
+        theme.critical,
+    ])
+}
+
+/// Rows needed to draw `core_count` cores in the given view, including the summary line.
+pub fn panel_height(core_count: usize, view: CoreView) -> u16 {
+    let rows = match view {
+        CoreView::Heatmap => core_count.div_ceil(HEATMAP_COLUMNS),
+        CoreView::Bars => core_count,
+    };
+    rows as u16 + 2
+}
+
+pub fn render(f: &mut Frame, area: Rect, cores: &[CoreInfo], governors: &[String], view: CoreView) {
+    let theme = theme::current();
+    let max_frequency = cores.iter().map(|core| core.frequency).max().unwrap_or(0);
+    let summary = format!(
+        "{} cores  gov: {}  max {}",
+        cores.len(),
+        if governors.is_empty() {
+            "n/a".to_string()
+        } else {
+            governors.join(",")
+        },
+        format_ghz(max_frequency)
+    );
+    let block = Block::default()
+        .borders(Borders::NONE)
+        .title(summary)
+        .title_style(Style::new().fg(theme.muted));
+
+    match view {
+        CoreView::Heatmap => {
+            let gradient = usage_gradient();
+            let lines: Vec<Line> = cores
+                .chunks(HEATMAP_COLUMNS)
+                .map(|row| {
+                    row.iter()
+                        .flat_map(|core| {
+                            let color = gradient.at(core.usage as f32 / 100.0);
+                            [Span::styled("██", Style::new().fg(color)), Span::raw(" ")]
+                        })
+                        .collect::<Vec<_>>()
+                        .into()
+                })
+                .collect();
+            f.render_widget(Paragraph::new(lines).block(block), area);
+        }
+        CoreView::Bars => {
+            let bars: Vec<Bar> = cores
+                .iter()
+                .enumerate()
+                .map(|(i, core)| {
+                    Bar::default()
+                        .label(format!("{:>2}", i).into())
+                        .value(core.usage)
+                        .text_value(format!("{} % {}", core.usage, format_ghz(core.frequency)))
+                })
+                .collect();
+            let chart = BarChart::default()
+                .block(block)
+                .direction(ratatui::layout::Direction::Horizontal)
+                .bar_width(1)
+                .bar_gap(0)
+                .bar_style(Style::new().fg(theme.muted).bg(theme.background))
+                .value_style(Style::new().fg(theme.background).bg(theme.muted))
+                .label_style(Style::new().fg(theme.muted))
+                .data(BarGroup::default().bars(&bars))
+                .max(100);
+            f.render_widget(chart, area);
+        }
+    }
+}
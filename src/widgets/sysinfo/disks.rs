@@ -0,0 +1,179 @@
+use crate::history::History;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+use sysinfo::Disks;
+
+/// `/proc/diskstats` counts in 512 byte sectors regardless of the device.
+const SECTOR_SIZE: u64 = 512;
+/// Polls over which the I/O bars are scaled, so one burst doesn't flatten
+/// them for the rest of the session.
+const PEAK_WINDOW: usize = 60;
+
+#[derive(Debug, Clone)]
+pub struct DiskEntry {
+    pub mount_point: String,
+    pub used_percent: u64,
+    pub available: u64,
+    /// Bytes per second since the previous poll.
+    pub read_rate: u64,
+    pub write_rate: u64,
+}
+
+fn read_diskstats() -> HashMap<String, (u64, u64)> {
+    fs::read_to_string("/proc/diskstats")
+        .map(|content| parse_diskstats(&content))
+        .unwrap_or_default()
+}
+
+/// Sectors read and written per block device name ("nvme0n1p2", "sda1").
+fn parse_diskstats(content: &str) -> HashMap<String, (u64, u64)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let sectors_read = fields.get(5)?.parse().ok()?;
+            let sectors_written = fields.get(9)?.parse().ok()?;
+            Some((fields.get(2)?.to_string(), (sectors_read, sectors_written)))
+        })
+        .collect()
+}
+
+/// Mounted filesystems with usage and I/O throughput of their block devices.
+pub struct DiskMonitor {
+    disks: Disks,
+    mounts: Vec<String>,
+    fs_types: Vec<String>,
+    previous: HashMap<String, (u64, u64)>,
+    previous_at: Instant,
+    pub entries: Vec<DiskEntry>,
+    /// Highest throughput of each recent poll.
+    peaks: History,
+}
+
+impl DiskMonitor {
+    pub fn new(mounts: Vec<String>, fs_types: Vec<String>) -> Self {
+        Self {
+            disks: Disks::new_with_refreshed_list(),
+            mounts,
+            fs_types,
+            previous: HashMap::new(),
+            previous_at: Instant::now(),
+            entries: Vec::new(),
+            peaks: History::new(PEAK_WINDOW),
+        }
+    }
+
+    /// Highest throughput over the last `PEAK_WINDOW` polls, used to scale the I/O bars.
+    pub fn peak_rate(&self) -> u64 {
+        self.peaks.peak()
+    }
+
+    /// Forgets the last sample while the disks panel is hidden, so the first
+    /// rates after showing it again don't average over the hidden period.
+    pub fn reset(&mut self) {
+        self.previous.clear();
+        self.peaks = History::new(PEAK_WINDOW);
+        self.entries.clear();
+    }
+
+    fn is_shown(&self, mount_point: &str, fs_type: &str) -> bool {
+        (self.mounts.is_empty() || self.mounts.iter().any(|m| m == mount_point))
+            && (self.fs_types.is_empty() || self.fs_types.iter().any(|t| t == fs_type))
+    }
+
+    pub fn refresh(&mut self) {
+        self.disks.refresh(true);
+        let stats = read_diskstats();
+        let elapsed = self.previous_at.elapsed().as_secs_f64().max(0.001);
+
+        let mut entries = Vec::new();
+        for disk in self.disks.list() {
+            let mount_point = disk.mount_point().to_string_lossy().to_string();
+            let fs_type = disk.file_system().to_string_lossy().to_string();
+            if !self.is_shown(&mount_point, &fs_type) {
+                continue;
+            }
+
+            let device = device_name(Path::new(disk.name()));
+            let device = device.as_str();
+            let rate = |current: u64, previous: u64| {
+                (current.saturating_sub(previous) as f64 * SECTOR_SIZE as f64 / elapsed) as u64
+            };
+            let (read_rate, write_rate) = match (stats.get(device), self.previous.get(device)) {
+                (Some(current), Some(previous)) => {
+                    (rate(current.0, previous.0), rate(current.1, previous.1))
+                }
+                _ => (0, 0),
+            };
+
+            let total = disk.total_space();
+            let used = total.saturating_sub(disk.available_space());
+            entries.push(DiskEntry {
+                mount_point,
+                used_percent: (used * 100).checked_div(total).unwrap_or(0),
+                available: disk.available_space(),
+                read_rate,
+                write_rate,
+            });
+        }
+
+        self.peaks.push(
+            entries
+                .iter()
+                .flat_map(|entry| [entry.read_rate, entry.write_rate])
+                .max()
+                .unwrap_or(0),
+        );
+        self.entries = entries;
+        self.previous = stats;
+        self.previous_at = Instant::now();
+    }
+}
+
+/// The kernel name of a block device as used in `/proc/diskstats`. Symlinks such
+/// as `/dev/mapper/cryptroot` resolve to the real node, `/dev/dm-0`.
+fn device_name(path: &Path) -> String {
+    let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_diskstats() {
+        let stats = parse_diskstats(
+            " 259       0 nvme0n1 180262 52016 14307398 40170 355130 262530 21564200 387510 0 186644 447700 0 0 0 0 12440 20018
+ 259       2 nvme0n1p2 179923 52016 14292518 40094 355130 262530 21564200 387510 0 186628 427604 0 0 0 0 0 0
+ 253       0 dm-0 231743 0 14290902 70952 617620 0 21564200 1302352 0 187460 1373304 0 0 0 0 0 0
+   7       0 loop0 12 0
+",
+        );
+        assert_eq!(stats.len(), 3);
+        assert_eq!(stats["nvme0n1p2"], (14292518, 21564200));
+        assert_eq!(stats["dm-0"], (14290902, 21564200));
+        assert!(!stats.contains_key("loop0"));
+    }
+
+    #[test]
+    fn resolves_device_symlinks() {
+        let dir = std::env::temp_dir().join(format!("gjpanel-dev-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("mapper")).unwrap();
+        fs::write(dir.join("dm-0"), "").unwrap();
+        std::os::unix::fs::symlink("../dm-0", dir.join("mapper/cryptroot")).unwrap();
+
+        assert_eq!(device_name(&dir.join("mapper/cryptroot")), "dm-0");
+        assert_eq!(device_name(&dir.join("dm-0")), "dm-0");
+        assert_eq!(
+            device_name(Path::new("/dev/gjpanel-missing1")),
+            "gjpanel-missing1"
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,37 @@
+use super::{DrmCard, GpuBackend, GpuStats, hwmon_temperature, read_string, read_u64};
+use crate::logger::Logger;
+
+/// amdgpu driver statistics from sysfs.
+pub struct AmdGpuBackend {
+    cards: Vec<DrmCard>,
+}
+
+impl AmdGpuBackend {
+    pub fn new(cards: Vec<DrmCard>) -> Self {
+        Self { cards }
+    }
+}
+
+impl GpuBackend for AmdGpuBackend {
+    fn name(&self) -> &'static str {
+        "amdgpu"
+    }
+
+    fn poll(&mut self, _logger: &Logger) -> Vec<GpuStats> {
+        self.cards
+            .iter()
+            .map(|card| {
+                let device = card.device();
+                GpuStats {
+                    name: read_string(&device.join("product_name"))
+                        .filter(|name| !name.is_empty())
+                        .unwrap_or_else(|| format!("AMD {}", card.name)),
+                    utilization: read_u64(&device.join("gpu_busy_percent")),
+                    temperature: hwmon_temperature(&device),
+                    memory_used: read_u64(&device.join("mem_info_vram_used")),
+                    memory_total: read_u64(&device.join("mem_info_vram_total")),
+                }
+            })
+            .collect()
+    }
+}
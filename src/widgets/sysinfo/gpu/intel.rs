@@ -0,0 +1,83 @@
+use super::{DrmCard, GpuBackend, GpuStats, hwmon_temperature, read_u64};
+use crate::logger::Logger;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Instant;
+
+/// i915 and xe driver statistics from sysfs. Neither exposes a busy percentage
+/// or memory usage, so utilization is derived from the time the GT spent idle
+/// (RC6) between polls and memory is not reported.
+pub struct IntelBackend {
+    cards: Vec<DrmCard>,
+    /// Idle residency in ms and when it was read, per card.
+    previous: HashMap<String, (u64, Instant)>,
+}
+
+impl IntelBackend {
+    pub fn new(cards: Vec<DrmCard>) -> Self {
+        Self {
+            cards,
+            previous: HashMap::new(),
+        }
+    }
+
+    /// Cumulative milliseconds the card's first GT spent idle.
+    fn idle_residency(card: &DrmCard) -> Option<u64> {
+        let candidates: [PathBuf; 3] = [
+            card.path.join("gt/gt0/rc6_residency_ms"),
+            card.path.join("power/rc6_residency_ms"),
+            card.device().join("tile0/gt0/gtidle/idle_residency_ms"),
+        ];
+        candidates.iter().find_map(|path| read_u64(path))
+    }
+
+    fn utilization(&mut self, card: &DrmCard) -> Option<u64> {
+        let idle = Self::idle_residency(card)?;
+        let now = Instant::now();
+        let previous = self.previous.insert(card.name.clone(), (idle, now));
+        let (previous_idle, previous_at) = previous?;
+        let elapsed = now.duration_since(previous_at).as_millis() as u64;
+        busy_percent(idle.saturating_sub(previous_idle), elapsed)
+    }
+}
+
+/// Share of `elapsed_ms` not spent idle, `None` before any time has passed.
+fn busy_percent(idle_ms: u64, elapsed_ms: u64) -> Option<u64> {
+    if elapsed_ms == 0 {
+        return None;
+    }
+    Some(100 - (idle_ms * 100 / elapsed_ms).min(100))
+}
+
+impl GpuBackend for IntelBackend {
+    fn name(&self) -> &'static str {
+        "intel"
+    }
+
+    fn poll(&mut self, _logger: &Logger) -> Vec<GpuStats> {
+        let cards = self.cards.clone();
+        cards
+            .iter()
+            .map(|card| GpuStats {
+                name: format!("Intel {} ({})", card.name, card.driver),
+                utilization: self.utilization(card),
+                temperature: hwmon_temperature(&card.device()),
+                memory_used: None,
+                memory_total: None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derives_busy_percent_from_idle_time() {
+        assert_eq!(busy_percent(1500, 2000), Some(25));
+        assert_eq!(busy_percent(0, 2000), Some(100));
+        assert_eq!(busy_percent(2100, 2000), Some(0));
+        assert_eq!(busy_percent(0, 0), None);
+    }
+}
@@ -0,0 +1,258 @@
+use crate::logger::Logger;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+mod amdgpu;
+mod intel;
+mod nvml;
+
+const VENDOR_AMD: u32 = 0x1002;
+const VENDOR_INTEL: u32 = 0x8086;
+const VENDOR_NVIDIA: u32 = 0x10de;
+
+/// One GPU's readings. Fields are `None` when the driver doesn't expose them.
+#[derive(Debug, Clone, Default)]
+pub struct GpuStats {
+    pub name: String,
+    /// Busy percentage, 0-100.
+    pub utilization: Option<u64>,
+    /// Degrees Celsius.
+    pub temperature: Option<u64>,
+    /// Bytes.
+    pub memory_used: Option<u64>,
+    pub memory_total: Option<u64>,
+}
+
+impl GpuStats {
+    pub fn memory_percent(&self) -> Option<u64> {
+        match (self.memory_used, self.memory_total) {
+            (Some(used), Some(total)) if total > 0 => {
+                Some((used as f64 / total as f64 * 100.0) as u64)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A source of GPU statistics, covering every GPU of one vendor/driver.
+pub trait GpuBackend {
+    fn name(&self) -> &'static str;
+    fn poll(&mut self, logger: &Logger) -> Vec<GpuStats>;
+    /// Why the last poll failed to read some statistics.
+    fn error(&self) -> Option<String> {
+        None
+    }
+}
+
+/// A `/sys/class/drm/cardN` entry.
+#[derive(Debug, Clone)]
+pub struct DrmCard {
+    pub name: String,
+    pub path: PathBuf,
+    pub vendor: u32,
+    pub driver: String,
+}
+
+impl DrmCard {
+    pub fn device(&self) -> PathBuf {
+        self.path.join("device")
+    }
+}
+
+/// Lists DRM cards under `<sysfs_root>/class/drm`, skipping connector entries.
+pub fn drm_cards(sysfs_root: &Path) -> Vec<DrmCard> {
+    let Ok(entries) = fs::read_dir(sysfs_root.join("class/drm")) else {
+        return Vec::new();
+    };
+    let mut cards: Vec<DrmCard> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let index = name.strip_prefix("card")?;
+            if index.is_empty() || !index.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            let path = entry.path();
+            let vendor = read_string(&path.join("device/vendor"))
+                .and_then(|v| u32::from_str_radix(v.trim_start_matches("0x"), 16).ok())?;
+            let driver = fs::read_link(path.join("device/driver"))
+                .ok()
+                .and_then(|link| link.file_name().map(|n| n.to_string_lossy().to_string()))
+                .unwrap_or_default();
+            Some(DrmCard {
+                name,
+                path,
+                vendor,
+                driver,
+            })
+        })
+        .collect();
+    cards.sort_by(|a, b| a.name.cmp(&b.name));
+    cards
+}
+
+/// Builds a backend for every GPU vendor found under `sysfs_root`. NVML is always
+/// tried since the proprietary driver doesn't always show up under `class/drm`;
+/// its failure is only an error when an NVIDIA card was found.
+pub fn detect_backends(sysfs_root: &Path, logger: &Logger) -> Vec<Box<dyn GpuBackend>> {
+    let cards = drm_cards(sysfs_root);
+    let mut backends: Vec<Box<dyn GpuBackend>> = Vec::new();
+
+    let amd: Vec<DrmCard> = cards
+        .iter()
+        .filter(|card| card.vendor == VENDOR_AMD && card.driver == "amdgpu")
+        .cloned()
+        .collect();
+    if !amd.is_empty() {
+        backends.push(Box::new(amdgpu::AmdGpuBackend::new(amd)));
+    }
+
+    let intel: Vec<DrmCard> = cards
+        .iter()
+        .filter(|card| {
+            card.vendor == VENDOR_INTEL && (card.driver == "i915" || card.driver == "xe")
+        })
+        .cloned()
+        .collect();
+    if !intel.is_empty() {
+        // Busy time comes from RC6 idle residency; i915 and xe have no memory statistics.
+        backends.push(Box::new(intel::IntelBackend::new(intel)));
+    }
+
+    match nvml::NvmlBackend::new() {
+        Ok(backend) => backends.push(Box::new(backend)),
+        Err(err) if cards.iter().any(|card| card.vendor == VENDOR_NVIDIA) => {
+            logger.error(&format!("Failed to initialize NVML: {}", err))
+        }
+        Err(err) => logger.debug(&format!("NVML not available: {}", err)),
+    }
+
+    for backend in &backends {
+        logger.info(&format!("Using GPU backend: {}", backend.name()));
+    }
+    backends
+}
+
+pub fn read_string(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok().map(|s| s.trim().to_string())
+}
+
+pub fn read_u64(path: &Path) -> Option<u64> {
+    read_string(path)?.parse().ok()
+}
+
+/// First `temp1_input` under the device's hwmon directory, in °C.
+pub fn hwmon_temperature(device: &Path) -> Option<u64> {
+    let entries = fs::read_dir(device.join("hwmon")).ok()?;
+    entries
+        .flatten()
+        .find_map(|entry| read_u64(&entry.path().join("temp1_input")))
+        .map(|millidegrees| millidegrees / 1000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logger::test_logger;
+    use std::os::unix::fs::symlink;
+
+    /// A fake sysfs root in the temp dir, removed when dropped.
+    struct FakeSysfs(PathBuf);
+
+    impl FakeSysfs {
+        fn new(name: &str) -> Self {
+            let root =
+                std::env::temp_dir().join(format!("gjpanel-sysfs-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&root);
+            fs::create_dir_all(root.join("class/drm")).unwrap();
+            Self(root)
+        }
+
+        fn write(&self, path: &str, content: &str) {
+            let path = self.0.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+
+        /// Adds `class/drm/<card>` with a `device/driver` link named `driver`.
+        fn card(&self, card: &str, vendor: &str, driver: &str) {
+            let device = format!("class/drm/{}/device", card);
+            self.write(&format!("{}/vendor", device), vendor);
+            let driver_dir = self.0.join("bus/pci/drivers").join(driver);
+            fs::create_dir_all(&driver_dir).unwrap();
+            symlink(driver_dir, self.0.join(device).join("driver")).unwrap();
+        }
+    }
+
+    impl Drop for FakeSysfs {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn lists_cards_and_skips_connectors() {
+        let sysfs = FakeSysfs::new("cards");
+        sysfs.card("card1", "0x8086\n", "i915");
+        sysfs.card("card0", "0x1002\n", "amdgpu");
+        sysfs.write("class/drm/card0-DP-1/status", "connected");
+        sysfs.write("class/drm/renderD128/dev", "226:128");
+
+        let cards = drm_cards(&sysfs.0);
+        let found: Vec<(&str, u32, &str)> = cards
+            .iter()
+            .map(|card| (card.name.as_str(), card.vendor, card.driver.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("card0", VENDOR_AMD, "amdgpu"),
+                ("card1", VENDOR_INTEL, "i915")
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_amdgpu_stats() {
+        let sysfs = FakeSysfs::new("amdgpu");
+        sysfs.card("card0", "0x1002", "amdgpu");
+        let device = "class/drm/card0/device";
+        sysfs.write(&format!("{}/gpu_busy_percent", device), "42\n");
+        sysfs.write(&format!("{}/mem_info_vram_used", device), "1073741824\n");
+        sysfs.write(&format!("{}/mem_info_vram_total", device), "4294967296\n");
+        sysfs.write(&format!("{}/hwmon/hwmon3/temp1_input", device), "55000\n");
+
+        let mut backend = amdgpu::AmdGpuBackend::new(drm_cards(&sysfs.0));
+        let stats = backend.poll(test_logger());
+        assert_eq!(stats.len(), 1);
+        let gpu = &stats[0];
+        assert_eq!(gpu.name, "AMD card0");
+        assert_eq!(gpu.utilization, Some(42));
+        assert_eq!(gpu.temperature, Some(55));
+        assert_eq!(gpu.memory_used, Some(1 << 30));
+        assert_eq!(gpu.memory_total, Some(4 << 30));
+        assert_eq!(gpu.memory_percent(), Some(25));
+    }
+
+    #[test]
+    fn reads_intel_stats() {
+        let sysfs = FakeSysfs::new("intel");
+        sysfs.card("card0", "0x8086", "xe");
+        sysfs.write("class/drm/card0/device/hwmon/hwmon1/temp1_input", "48500\n");
+        let idle = "class/drm/card0/device/tile0/gt0/gtidle/idle_residency_ms";
+        sysfs.write(idle, "1000\n");
+
+        let mut backend = intel::IntelBackend::new(drm_cards(&sysfs.0));
+        let stats = backend.poll(test_logger());
+        assert_eq!(stats.len(), 1);
+        let gpu = &stats[0];
+        assert_eq!(gpu.name, "Intel card0 (xe)");
+        assert_eq!(gpu.utilization, None);
+        assert_eq!(gpu.temperature, Some(48));
+        assert_eq!(gpu.memory_percent(), None);
+
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        let stats = backend.poll(test_logger());
+        assert_eq!(stats[0].utilization, Some(100));
+    }
+}
@@ -0,0 +1,89 @@
+use super::{GpuBackend, GpuStats};
+use crate::logger::Logger;
+use nvml_wrapper::Nvml;
+use nvml_wrapper::enum_wrappers::device::TemperatureSensor;
+use nvml_wrapper::error::NvmlError;
+
+/// NVIDIA statistics through NVML, covering every device it reports.
+pub struct NvmlBackend {
+    nvml: Nvml,
+    error: Option<String>,
+}
+
+impl NvmlBackend {
+    pub fn new() -> Result<Self, NvmlError> {
+        Ok(Self {
+            nvml: Nvml::init()?,
+            error: None,
+        })
+    }
+}
+
+impl GpuBackend for NvmlBackend {
+    fn name(&self) -> &'static str {
+        "nvml"
+    }
+
+    fn poll(&mut self, logger: &Logger) -> Vec<GpuStats> {
+        let mut error = None;
+        let mut fail = |message: String| {
+            logger.error(&message);
+            error = Some(message);
+        };
+        let count = match self.nvml.device_count() {
+            Ok(count) => count,
+            Err(err) => {
+                fail(format!("Failed to get GPU device count: {}", err));
+                self.error = error;
+                return Vec::new();
+            }
+        };
+
+        let stats = (0..count)
+            .filter_map(|index| match self.nvml.device_by_index(index) {
+                Ok(device) => {
+                    let mut stats = GpuStats {
+                        name: device
+                            .name()
+                            .unwrap_or_else(|_| format!("NVIDIA {}", index)),
+                        ..GpuStats::default()
+                    };
+                    match device.memory_info() {
+                        Ok(memory_info) => {
+                            stats.memory_used = Some(memory_info.used);
+                            stats.memory_total = Some(memory_info.total);
+                        }
+                        Err(err) => {
+                            fail(format!("Failed to get GPU memory info: {}", err));
+                        }
+                    }
+                    match device.temperature(TemperatureSensor::Gpu) {
+                        Ok(temperature) => stats.temperature = Some(temperature as u64),
+                        Err(err) => {
+                            fail(format!("Failed to get GPU temperature: {}", err));
+                        }
+                    }
+                    match device.utilization_rates() {
+                        Ok(utilization_rates) => {
+                            stats.utilization = Some(utilization_rates.gpu as u64)
+                        }
+                        Err(err) => {
+                            fail(format!("Failed to get GPU utilization rates: {}", err));
+                        }
+                    }
+                    Some(stats)
+                }
+                Err(err) => {
+                    fail(format!("Failed to get GPU device: {}", err));
+                    None
+                }
+            })
+            .collect();
+        self.error = error;
+        stats
+    }
+
+    fn error(&self) -> Option<String> {
+        self.error.clone()
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::format::format_bytes;
+
+#[derive(Debug, Clone)]
+pub struct ZramDevice {
+    pub name: String,
+    /// Uncompressed bytes stored in the device.
+    pub original: u64,
+    pub compressed: u64,
+}
+
+/// Memory breakdown for the expanded view. Sizes are in bytes, fields are
+/// `None` when the kernel does not report them.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryDetails {
+    pub total: Option<u64>,
+    pub available: Option<u64>,
+    pub cached: Option<u64>,
+    pub buffers: Option<u64>,
+    pub swap_total: Option<u64>,
+    pub swap_free: Option<u64>,
+    pub hugepages_total: Option<u64>,
+    pub hugepages_free: Option<u64>,
+    pub hugepage_size: Option<u64>,
+    pub zram: Vec<ZramDevice>,
+}
+
+/// Parses `/proc/meminfo` lines such as "MemAvailable:   123456 kB". Values
+/// with a kB suffix are converted to bytes, plain counts are kept as is.
+pub fn parse_meminfo(content: &str) -> HashMap<String, u64> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (key, rest) = line.split_once(':')?;
+            let mut fields = rest.split_whitespace();
+            let value: u64 = fields.next()?.parse().ok()?;
+            let value = match fields.next() {
+                Some("kB") => value * 1024,
+                _ => value,
+            };
+            Some((key.trim().to_string(), value))
+        })
+        .collect()
+}
+
+/// zram devices from `/sys/block/zram*/mm_stat`, whose first two fields are
+/// the original and compressed data sizes.
+fn read_zram(sys_block: &Path) -> Vec<ZramDevice> {
+    let Ok(entries) = fs::read_dir(sys_block) else {
+        return Vec::new();
+    };
+    let mut devices: Vec<ZramDevice> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !name.starts_with("zram") {
+                return None;
+            }
+            let stat = fs::read_to_string(entry.path().join("mm_stat")).ok()?;
+            let mut fields = stat.split_whitespace();
+            Some(ZramDevice {
+                name,
+                original: fields.next()?.parse().ok()?,
+                compressed: fields.next()?.parse().ok()?,
+            })
+        })
+        .collect();
+    devices.sort_by(|a, b| a.name.cmp(&b.name));
+    devices
+}
+
+impl MemoryDetails {
+    pub fn read() -> Self {
+        let meminfo = fs::read_to_string("/proc/meminfo").unwrap_or_default();
+        Self::parse(&meminfo, Path::new("/sys/block"))
+    }
+
+    fn parse(meminfo: &str, sys_block: &Path) -> Self {
+        let fields = parse_meminfo(meminfo);
+        let get = |key: &str| fields.get(key).copied();
+        Self {
+            total: get("MemTotal"),
+            available: get("MemAvailable"),
+            cached: get("Cached"),
+            buffers: get("Buffers"),
+            swap_total: get("SwapTotal"),
+            swap_free: get("SwapFree"),
+            hugepages_total: get("HugePages_Total"),
+            hugepages_free: get("HugePages_Free"),
+            hugepage_size: get("Hugepagesize"),
+            zram: read_zram(sys_block),
+        }
+    }
+
+    /// One line per reported detail, skipping what this machine does not have.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let (Some(total), Some(available)) = (self.total, self.available) {
+            lines.push(format!(
+                "avail {} / {}",
+                format_bytes(available),
+                format_bytes(total)
+            ));
+        }
+        if let Some(cached) = self.cached {
+            lines.push(format!(
+                "cached {}  buffers {}",
+                format_bytes(cached),
+                format_bytes(self.buffers.unwrap_or(0))
+            ));
+        }
+        match (self.swap_total, self.swap_free) {
+            (Some(total), Some(free)) if total > 0 => lines.push(format!(
+                "swap {} / {}",
+                format_bytes(total - free.min(total)),
+                format_bytes(total)
+            )),
+            _ => lines.push("swap none".to_string()),
+        }
+        for device in &self.zram {
+            let ratio = if device.compressed > 0 {
+                device.original as f64 / device.compressed as f64
+            } else {
+                0.0
+            };
+            lines.push(format!(
+                "{} {} -> {} ({:.1}x)",
+                device.name,
+                format_bytes(device.original),
+                format_bytes(device.compressed),
+                ratio
+            ));
+        }
+        if let (Some(total), Some(free)) = (self.hugepages_total, self.hugepages_free)
+            && total > 0
+        {
+            lines.push(format!(
+                "hugepages {}/{} free ({} each)",
+                free,
+                total,
+                format_bytes(self.hugepage_size.unwrap_or(0))
+            ));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEMINFO: &str = "\
+MemTotal:       16309248 kB
+MemFree:         1183744 kB
+MemAvailable:    9437184 kB
+Buffers:          262144 kB
+Cached:          7340032 kB
+SwapTotal:       8388604 kB
+SwapFree:        8388604 kB
+HugePages_Total:       4
+HugePages_Free:        3
+Hugepagesize:       2048 kB
+";
+
+    #[test]
+    fn scales_kb_values_and_keeps_counts() {
+        let fields = parse_meminfo(MEMINFO);
+        assert_eq!(fields["MemTotal"], 16309248 * 1024);
+        assert_eq!(fields["HugePages_Total"], 4);
+        assert_eq!(fields["Hugepagesize"], 2 << 20);
+        assert!(parse_meminfo("Broken line\nNoValue:\n").is_empty());
+    }
+
+    #[test]
+    fn reads_details_with_zram() {
+        let sys_block = std::env::temp_dir().join(format!("gjpanel-block-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&sys_block);
+        for (name, stat) in [
+            ("zram0", "4194304 1048576 1200000 0 1500000 0 0 0 0"),
+            ("sda", ""),
+        ] {
+            fs::create_dir_all(sys_block.join(name)).unwrap();
+            fs::write(sys_block.join(name).join("mm_stat"), stat).unwrap();
+        }
+
+        let details = MemoryDetails::parse(MEMINFO, &sys_block);
+        let _ = fs::remove_dir_all(&sys_block);
+        assert_eq!(details.available, Some(9 << 30));
+        assert_eq!(details.hugepages_free, Some(3));
+        assert_eq!(details.zram.len(), 1);
+        assert_eq!(details.zram[0].original, 4 << 20);
+        assert_eq!(
+            details.lines(),
+            vec![
+                "avail 9.0G / 15.6G",
+                "cached 7.0G  buffers 256.0M",
+                "swap 0B / 8.0G",
+                "zram0 4.0M -> 1.0M (4.0x)",
+                "hugepages 3/4 free (2.0M each)",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_missing_swap() {
+        let details = MemoryDetails::parse("MemTotal: 1024 kB\n", Path::new("/nonexistent"));
+        assert_eq!(details.available, None);
+        assert_eq!(details.lines(), vec!["swap none"]);
+    }
+}
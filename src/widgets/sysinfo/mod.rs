@@ -0,0 +1,659 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
+    widgets::{Bar, BarChart, BarGroup, Block, Borders, Padding, Paragraph, Sparkline},
+};
+use std::collections::HashMap;
+use std::path::Path;
+use sysinfo::{
+    Components, CpuRefreshKind, MemoryRefreshKind, ProcessRefreshKind, RefreshKind, System,
+};
+
+use crate::config::{CoreView, SysInfoConfig, SysInfoView};
+use crate::format::format_bytes;
+use crate::logger::Logger;
+use crate::theme;
+
+use super::{GJWidget, WidgetErrors};
+
+mod alerts;
+mod cores;
+mod disks;
+mod gpu;
+mod meminfo;
+mod processes;
+mod sensors;
+
+use crate::history::History;
+use alerts::Alerts;
+use cores::CoreInfo;
+use disks::DiskMonitor;
+use gpu::{GpuBackend, GpuStats};
+use meminfo::MemoryDetails;
+use processes::ProcessList;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Metric {
+    CpuLoad,
+    CpuTemp,
+    GpuUtil,
+    GpuTemp,
+    GpuMem,
+    MemUsage,
+    SwapUsage,
+}
+
+impl Metric {
+    const CPU: [Metric; 2] = [Metric::CpuLoad, Metric::CpuTemp];
+    const GPU: [Metric; 3] = [Metric::GpuUtil, Metric::GpuTemp, Metric::GpuMem];
+    const MEM: [Metric; 2] = [Metric::MemUsage, Metric::SwapUsage];
+
+    fn label(self) -> &'static str {
+        match self {
+            Metric::CpuLoad => "CPU 󰍛",
+            Metric::CpuTemp => "CPU ",
+            Metric::GpuUtil => "GPU 󱡶",
+            Metric::GpuTemp => "GPU ",
+            Metric::GpuMem => "VRAM",
+            Metric::MemUsage => "MEM ",
+            Metric::SwapUsage => "SWAP",
+        }
+    }
+
+    /// Name used for the metric in `[sysinfo.thresholds]`.
+    fn key(self) -> &'static str {
+        match self {
+            Metric::CpuLoad => "cpu_load",
+            Metric::CpuTemp => "cpu_temp",
+            Metric::GpuUtil => "gpu_util",
+            Metric::GpuTemp => "gpu_temp",
+            Metric::GpuMem => "gpu_mem",
+            Metric::MemUsage => "mem",
+            Metric::SwapUsage => "swap",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        [Metric::CPU.as_slice(), &Metric::GPU, &Metric::MEM]
+            .concat()
+            .into_iter()
+            .find(|metric| metric.key() == key)
+    }
+
+    fn unit(self) -> &'static str {
+        match self {
+            Metric::CpuTemp | Metric::GpuTemp => "°C",
+            _ => "%",
+        }
+    }
+}
+
+pub struct SysInfoWidget {
+    logger: &'static Logger,
+    view: SysInfoView,
+    /// Keyed by metric and device index (GPU number, 0 for CPU and memory).
+    histories: HashMap<(Metric, usize), History>,
+    history_length: usize,
+    show_cores: bool,
+    core_view: CoreView,
+    cores: Vec<CoreInfo>,
+    governors: Vec<String>,
+    show_sensors: bool,
+    system: System,
+    components: Components,
+    cpu_sensor_index: Option<usize>,
+    component_info: String,
+    mem_percent: Option<u64>,
+    /// `None` on machines without swap.
+    swap_percent: Option<u64>,
+    memory_details: MemoryDetails,
+    show_memory_details: bool,
+    kernel_version: String,
+    cpu_load: u64,
+    cpu_temp: Option<u64>,
+    gpu_backends: Vec<Box<dyn GpuBackend>>,
+    gpus: Vec<GpuStats>,
+    disks: DiskMonitor,
+    show_disks: bool,
+    processes: ProcessList,
+    show_processes: bool,
+    alerts: Alerts,
+    errors: WidgetErrors,
+}
+
+impl SysInfoWidget {
+    pub fn new(config: SysInfoConfig, logger: &'static Logger) -> Self {
+        let components = Components::new_with_refreshed_list();
+        let cpu_sensor_index = sensors::find_cpu_sensor(&components, config.cpu_sensor.as_deref());
+        if components.is_empty() {
+            logger.error("No components detected.");
+        } else if cpu_sensor_index.is_none() {
+            logger.error(&format!(
+                "No CPU temperature sensor matching '{}'",
+                config.cpu_sensor.as_deref().unwrap_or("auto")
+            ));
+        }
+
+        Self {
+            logger,
+            alerts: Alerts::new(&config, logger),
+            view: config.view,
+            show_cores: config.show_cores,
+            core_view: config.core_view,
+            cores: Vec::new(),
+            governors: Vec::new(),
+            show_sensors: false,
+            histories: HashMap::new(),
+            history_length: config.history_length,
+            system: System::new(),
+            components,
+            cpu_sensor_index,
+            component_info: String::new(),
+            mem_percent: None,
+            swap_percent: None,
+            memory_details: MemoryDetails::default(),
+            show_memory_details: config.show_memory_details,
+            kernel_version: System::kernel_version().unwrap_or_default(),
+            cpu_load: 0,
+            cpu_temp: None,
+            gpu_backends: gpu::detect_backends(Path::new("/sys"), logger),
+            gpus: Vec::new(),
+            disks: DiskMonitor::new(config.disk_mounts, config.disk_fs_types),
+            show_disks: config.show_disks,
+            processes: ProcessList::new(config.process_sort, config.process_count),
+            show_processes: config.show_processes,
+            errors: WidgetErrors::default(),
+        }
+    }
+
+    /// Only the data shown is refreshed: CPU usage always, frequencies for the
+    /// cores panel, memory, and the process table when it is open.
+    fn refresh_kind(&self) -> RefreshKind {
+        let mut cpu = CpuRefreshKind::nothing().with_cpu_usage();
+        if self.show_cores {
+            cpu = cpu.with_frequency();
+        }
+        let mut kind = RefreshKind::nothing()
+            .with_cpu(cpu)
+            .with_memory(MemoryRefreshKind::everything());
+        if self.show_processes {
+            kind = kind.with_processes(ProcessRefreshKind::nothing().with_cpu().with_memory());
+        }
+        kind
+    }
+
+    fn set_cpu_usage(&mut self) {
+        let mut cpu_usage = 0.0;
+        for cpu in self.system.cpus() {
+            cpu_usage += cpu.cpu_usage();
+        }
+        self.cpu_load = (cpu_usage / self.system.cpus().len().max(1) as f32) as u64;
+        self.cores = self.system.cpus().iter().map(CoreInfo::from_cpu).collect();
+        if self.show_cores {
+            self.governors = cores::read_governors(self.cores.len());
+        }
+    }
+
+    /// Refreshes the CPU sensor, or every component while the sensors panel is open.
+    fn set_temperatures(&mut self) {
+        if self.show_sensors {
+            self.components.refresh(false);
+        } else if let Some(index) = self.cpu_sensor_index {
+            self.components.list_mut()[index].refresh();
+        }
+
+        if let Some(index) = self.cpu_sensor_index {
+            let sensor = &self.components.list()[index];
+            self.cpu_temp = sensor.temperature().map(|temperature| temperature as u64);
+            if self.cpu_temp.is_some() {
+                self.errors.clear("cpu sensor");
+            } else {
+                let message = format!("CPU sensor '{}' has no temperature reading", sensor.label());
+                self.logger.error(&message);
+                self.errors.set("cpu sensor", &message);
+            }
+        }
+
+        if !self.show_sensors {
+            return;
+        }
+        self.component_info = String::new();
+        for component in &self.components {
+            let reading =
+                |value: Option<f32>| value.map_or("n/a".to_string(), |v| format!("{:.1}°C", v));
+            self.component_info.push_str(&format!(
+                "{}: {} (max: {} / crit: {})\n",
+                component.label(),
+                reading(component.temperature()),
+                reading(component.max()),
+                reading(component.critical()) // Critical temp might not be available
+            ));
+        }
+    }
+    fn set_gpu_usage(&mut self) {
+        self.gpus = self
+            .gpu_backends
+            .iter_mut()
+            .flat_map(|backend| backend.poll(self.logger))
+            .collect();
+        match self.gpu_backends.iter().find_map(|backend| backend.error()) {
+            Some(error) => self.errors.set("gpu", &error),
+            None => self.errors.clear("gpu"),
+        }
+    }
+
+    /// The current reading, `None` when the hardware or sensor is absent.
+    fn value(&self, metric: Metric, device: usize) -> Option<u64> {
+        let gpu = self.gpus.get(device);
+        match metric {
+            Metric::CpuLoad => Some(self.cpu_load),
+            Metric::CpuTemp => self.cpu_temp,
+            Metric::GpuUtil => gpu.and_then(|gpu| gpu.utilization),
+            Metric::GpuTemp => gpu.and_then(|gpu| gpu.temperature),
+            Metric::GpuMem => gpu.and_then(|gpu| gpu.memory_percent()),
+            Metric::MemUsage => self.mem_percent,
+            Metric::SwapUsage => self.swap_percent,
+        }
+    }
+
+    /// Every (metric, device) pair with a reading, in display order.
+    fn metric_keys(&self) -> Vec<(Metric, usize)> {
+        let mut keys: Vec<(Metric, usize)> = Metric::CPU.iter().map(|m| (*m, 0)).collect();
+        for device in 0..self.gpus.len() {
+            keys.extend(Metric::GPU.iter().map(|m| (*m, device)));
+        }
+        keys.extend(Metric::MEM.iter().map(|m| (*m, 0)));
+        keys.retain(|(metric, device)| self.value(*metric, *device).is_some());
+        keys
+    }
+
+    fn gpu_label(&self, device: usize) -> String {
+        if self.gpus.len() > 1 {
+            format!("GPU{} 󱡶", device)
+        } else {
+            "GPU 󱡶".to_string()
+        }
+    }
+
+    fn metric_label(&self, metric: Metric, device: usize) -> String {
+        if self.gpus.len() > 1 && Metric::GPU.contains(&metric) {
+            format!("{}{}", metric.label(), device)
+        } else {
+            metric.label().to_string()
+        }
+    }
+
+    /// A bar for one metric, colored by its threshold level.
+    fn metric_bar(&self, metric: Metric, device: usize, suffix: &str) -> Option<Bar<'static>> {
+        let theme = theme::current();
+        let value = self.value(metric, device)?;
+        let bar = Bar::default()
+            .value(value)
+            .text_value(format!("{} {}", value, suffix))
+            .style(self.alerts.style(metric, device, Style::new()))
+            .value_style(self.alerts.value_style(
+                metric,
+                device,
+                Style::new().fg(theme.background).bg(theme.muted),
+            ));
+        Some(bar)
+    }
+
+    /// A labelled group of the metrics that have readings, `None` when none do.
+    fn metric_group(
+        &self,
+        label: String,
+        bars: &[(Metric, usize, &str)],
+    ) -> Option<BarGroup<'static>> {
+        let bars: Vec<Bar> = bars
+            .iter()
+            .filter_map(|(metric, device, suffix)| self.metric_bar(*metric, *device, suffix))
+            .collect();
+        if bars.is_empty() {
+            return None;
+        }
+        Some(BarGroup::default().label(label.into()).bars(&bars))
+    }
+
+    fn record_history(&mut self) {
+        for key in self.metric_keys() {
+            let Some(value) = self.value(key.0, key.1) else {
+                continue;
+            };
+            let label = self.metric_label(key.0, key.1);
+            self.alerts.update(key.0, key.1, &label, value, self.logger);
+            self.histories
+                .entry(key)
+                .or_insert_with(|| History::new(self.history_length))
+                .push(value);
+        }
+    }
+
+    fn render_graphs(&self, f: &mut Frame, area: Rect) {
+        let theme = theme::current();
+        let block = Block::default()
+            .borders(Borders::NONE)
+            .title(self.kernel_version.clone())
+            .title_style(Style::new().fg(theme.muted).bg(theme.background))
+            .title_alignment(Alignment::Left)
+            .padding(Padding::top(2));
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        let keys = self.metric_keys();
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Length(2); keys.len()])
+            .split(inner);
+
+        for ((metric, device), row) in keys.iter().zip(rows.iter()) {
+            let (Some(history), Some(value)) = (
+                self.histories.get(&(*metric, *device)),
+                self.value(*metric, *device),
+            ) else {
+                continue;
+            };
+            let label = self.metric_label(*metric, *device);
+            let title = format!(
+                "{} {} {} (peak {})",
+                label,
+                value,
+                metric.unit(),
+                history.peak()
+            );
+            let sparkline = Sparkline::default()
+                .block(
+                    Block::default()
+                        .borders(Borders::NONE)
+                        .title(title)
+                        .title_style(Style::new().fg(theme.muted)),
+                )
+                .data(history.tail(row.width as usize))
+                .max(100)
+                .style(self.alerts.style(
+                    *metric,
+                    *device,
+                    Style::new().fg(theme.muted).bg(theme.background),
+                ));
+            f.render_widget(sparkline, *row);
+        }
+    }
+
+    fn set_memory_usage(&mut self) {
+        self.mem_percent =
+            (self.system.used_memory() * 100).checked_div(self.system.total_memory());
+        self.swap_percent = (self.system.used_swap() * 100).checked_div(self.system.total_swap());
+        if self.show_memory_details {
+            self.memory_details = MemoryDetails::read();
+        }
+    }
+}
+
+impl GJWidget for SysInfoWidget {
+    fn error(&self) -> Option<String> {
+        self.errors.current()
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> bool {
+        if self.show_processes && self.processes.is_confirming() {
+            match key_event.code {
+                KeyCode::Char('y') => match self.processes.confirm_kill(&self.system) {
+                    Ok(message) => {
+                        self.logger.info(&message);
+                        self.errors.clear("kill");
+                    }
+                    Err(message) => {
+                        self.logger.error(&message);
+                        self.errors.set("kill", &message);
+                    }
+                },
+                _ => self.processes.cancel_kill(),
+            }
+            return true;
+        }
+        match key_event.code {
+            KeyCode::Char('g') => {
+                self.view = match self.view {
+                    SysInfoView::Bars => SysInfoView::Graphs,
+                    SysInfoView::Graphs => SysInfoView::Bars,
+                };
+            }
+            KeyCode::Char('c') => {
+                self.show_cores = !self.show_cores;
+                if self.show_cores {
+                    self.governors = cores::read_governors(self.cores.len());
+                }
+            }
+            KeyCode::Char('m') => {
+                self.show_memory_details = !self.show_memory_details;
+                if self.show_memory_details {
+                    self.memory_details = MemoryDetails::read();
+                }
+            }
+            KeyCode::Char('t') => {
+                self.show_sensors = !self.show_sensors;
+                self.set_temperatures();
+            }
+            KeyCode::Char('d') => {
+                self.show_disks = !self.show_disks;
+                if self.show_disks {
+                    self.disks.refresh();
+                } else {
+                    self.disks.reset();
+                }
+            }
+            KeyCode::Char('p') => {
+                self.show_processes = !self.show_processes;
+                if self.show_processes {
+                    // Processes are skipped by the poll refresh while the panel is hidden.
+                    self.system
+                        .refresh_specifics(self.refresh_kind().without_cpu().without_memory());
+                    self.processes.refresh(&self.system);
+                }
+            }
+            KeyCode::Char('s') if self.show_processes => {
+                self.processes.toggle_sort();
+                self.processes.refresh(&self.system);
+            }
+            KeyCode::Char('j') | KeyCode::Down if self.show_processes => {
+                self.processes.select_next()
+            }
+            KeyCode::Char('k') | KeyCode::Up if self.show_processes => {
+                self.processes.select_previous()
+            }
+            KeyCode::Char('K') if self.show_processes => self.processes.request_kill(),
+            KeyCode::Char('v') if self.show_cores => {
+                self.core_view = match self.core_view {
+                    CoreView::Heatmap => CoreView::Bars,
+                    CoreView::Bars => CoreView::Heatmap,
+                };
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn render(&self, f: &mut Frame, area: Rect) {
+        let theme = theme::current();
+        let area = if self.show_sensors {
+            let mut sensor_lines: Vec<String> =
+                self.component_info.lines().map(str::to_string).collect();
+            sensor_lines.extend(self.gpus.iter().enumerate().map(|(i, gpu)| {
+                format!(
+                    "GPU{} {}: {}",
+                    i,
+                    gpu.name,
+                    gpu.temperature
+                        .map_or("n/a".to_string(), |t| format!("{}°C", t))
+                )
+            }));
+            let [main, detail] = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(0),
+                    Constraint::Length(sensor_lines.len() as u16 + 1),
+                ])
+                .areas(area);
+            let sensors = Paragraph::new(sensor_lines.join("\n"))
+                .style(Style::new().fg(theme.muted))
+                .block(
+                    Block::default()
+                        .borders(Borders::NONE)
+                        .title("Sensors")
+                        .title_style(Style::new().fg(theme.muted)),
+                );
+            f.render_widget(sensors, detail);
+            main
+        } else {
+            area
+        };
+
+        let area = if self.show_memory_details {
+            let lines = self.memory_details.lines();
+            let [main, detail] = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(0),
+                    Constraint::Length(lines.len() as u16 + 1),
+                ])
+                .areas(area);
+            let details = Paragraph::new(lines.join("\n"))
+                .style(Style::new().fg(theme.muted))
+                .block(
+                    Block::default()
+                        .borders(Borders::NONE)
+                        .title("Memory")
+                        .title_style(Style::new().fg(theme.muted)),
+                );
+            f.render_widget(details, detail);
+            main
+        } else {
+            area
+        };
+
+        let area = if self.show_processes {
+            let [main, detail] = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(0),
+                    Constraint::Length(self.processes.panel_height()),
+                ])
+                .areas(area);
+            self.processes.render(f, detail);
+            main
+        } else {
+            area
+        };
+
+        let area = if self.show_cores {
+            let panel_height = cores::panel_height(self.cores.len(), self.core_view);
+            let [main, detail] = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(panel_height)])
+                .areas(area);
+            cores::render(f, detail, &self.cores, &self.governors, self.core_view);
+            main
+        } else {
+            area
+        };
+
+        if self.view == SysInfoView::Graphs {
+            self.render_graphs(f, area);
+            return;
+        }
+
+        let mut groups: Vec<BarGroup> = Vec::new();
+        groups.extend(self.metric_group(
+            "CPU 󰍛".to_string(),
+            &[(Metric::CpuLoad, 0, "%"), (Metric::CpuTemp, 0, "°C")],
+        ));
+        for device in 0..self.gpus.len() {
+            groups.extend(self.metric_group(
+                self.gpu_label(device),
+                &[
+                    (Metric::GpuUtil, device, "%"),
+                    (Metric::GpuTemp, device, "°C"),
+                    (Metric::GpuMem, device, "MB%"),
+                ],
+            ));
+        }
+        groups.extend(self.metric_group(
+            "MEM ".to_string(),
+            &[(Metric::MemUsage, 0, "%"), (Metric::SwapUsage, 0, "S%")],
+        ));
+
+        let disk_bar_groups: Vec<BarGroup> = if self.show_disks {
+            let peak = self.disks.peak_rate().max(1);
+            self.disks
+                .entries
+                .iter()
+                .map(|disk| {
+                    BarGroup::default()
+                        .label(format!("DSK {}", disk.mount_point).into())
+                        .bars(&[
+                            Bar::default().value(disk.used_percent).text_value(format!(
+                                "{} % {} free",
+                                disk.used_percent,
+                                format_bytes(disk.available)
+                            )),
+                            Bar::default()
+                                .value(disk.read_rate * 100 / peak)
+                                .text_value(format!("R {}/s", format_bytes(disk.read_rate))),
+                            Bar::default()
+                                .value(disk.write_rate * 100 / peak)
+                                .text_value(format!("W {}/s", format_bytes(disk.write_rate))),
+                        ])
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let mut bar_chart = BarChart::default()
+            .block(
+                Block::default()
+                    .borders(Borders::NONE)
+                    .title(self.kernel_version.clone())
+                    .title_style(Style::new().fg(theme.muted).bg(theme.background))
+                    .title_alignment(Alignment::Left)
+                    .padding(Padding::top(2)),
+            )
+            .direction(ratatui::layout::Direction::Horizontal)
+            .bar_width(1)
+            .bar_gap(0)
+            .group_gap(2)
+            .bar_style(Style::new().fg(theme.muted).bg(theme.background))
+            .value_style(Style::new().fg(theme.background).bg(theme.muted))
+            .label_style(Style::new().fg(theme.muted))
+            .max(100);
+        for group in groups {
+            bar_chart = bar_chart.data(group);
+        }
+        for disk_bar_group in disk_bar_groups {
+            bar_chart = bar_chart.data(disk_bar_group);
+        }
+
+        // Render the Paragraph widget in the given area
+        f.render_widget(bar_chart, area);
+        //f.render_widget(deb, area);
+    }
+    fn poll(&mut self) {
+        self.system.refresh_specifics(self.refresh_kind());
+
+        self.set_cpu_usage();
+        self.set_temperatures();
+        self.set_memory_usage();
+        self.set_gpu_usage();
+        if self.show_disks {
+            self.disks.refresh();
+        }
+        if self.show_processes {
+            self.processes.refresh(&self.system);
+        }
+        self.record_history();
+
+        //let cpu_usage = self.system.global_cpu_info().cpu_usage();
+    }
+}
@@ -0,0 +1,161 @@
+use crate::config::ProcessSort;
+use crate::format::format_bytes;
+use crate::theme;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Borders, Paragraph},
+};
+use sysinfo::{Pid, Signal, System};
+
+#[derive(Debug, Clone)]
+pub struct ProcessInfo {
+    pub pid: Pid,
+    pub name: String,
+    pub cpu_usage: f32,
+    /// Resident set size in bytes.
+    pub memory: u64,
+}
+
+/// The top-N process table and its selection and kill confirmation state.
+pub struct ProcessList {
+    entries: Vec<ProcessInfo>,
+    sort: ProcessSort,
+    count: usize,
+    selected: usize,
+    /// Process awaiting a y/n confirmation before SIGTERM is sent.
+    pending_kill: Option<ProcessInfo>,
+}
+
+impl ProcessList {
+    pub fn new(sort: ProcessSort, count: usize) -> Self {
+        Self {
+            entries: Vec::new(),
+            sort,
+            count: count.max(1),
+            selected: 0,
+            pending_kill: None,
+        }
+    }
+
+    pub fn refresh(&mut self, system: &System) {
+        let mut entries: Vec<ProcessInfo> = system
+            .processes()
+            .values()
+            .filter(|process| process.thread_kind().is_none())
+            .map(|process| ProcessInfo {
+                pid: process.pid(),
+                name: process.name().to_string_lossy().to_string(),
+                cpu_usage: process.cpu_usage(),
+                memory: process.memory(),
+            })
+            .collect();
+        match self.sort {
+            ProcessSort::Cpu => entries.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage)),
+            ProcessSort::Memory => entries.sort_by_key(|process| std::cmp::Reverse(process.memory)),
+        }
+        entries.truncate(self.count);
+        self.selected = self.selected.min(entries.len().saturating_sub(1));
+        self.entries = entries;
+    }
+
+    pub fn toggle_sort(&mut self) {
+        self.sort = match self.sort {
+            ProcessSort::Cpu => ProcessSort::Memory,
+            ProcessSort::Memory => ProcessSort::Cpu,
+        };
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.entries.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn is_confirming(&self) -> bool {
+        self.pending_kill.is_some()
+    }
+
+    /// Asks for confirmation before terminating the selected process.
+    pub fn request_kill(&mut self) {
+        self.pending_kill = self.entries.get(self.selected).cloned();
+    }
+
+    pub fn cancel_kill(&mut self) {
+        self.pending_kill = None;
+    }
+
+    /// Sends SIGTERM to the process awaiting confirmation.
+    pub fn confirm_kill(&mut self, system: &System) -> Result<String, String> {
+        let Some(target) = self.pending_kill.take() else {
+            return Err("No process selected".to_string());
+        };
+        let process = system
+            .process(target.pid)
+            .ok_or_else(|| format!("Process {} ({}) is gone", target.name, target.pid))?;
+        match process.kill_with(Signal::Term) {
+            Some(true) => Ok(format!("Sent SIGTERM to {} ({})", target.name, target.pid)),
+            Some(false) => Err(format!(
+                "Failed to send SIGTERM to {} ({})",
+                target.name, target.pid
+            )),
+            None => Err("SIGTERM is not supported on this platform".to_string()),
+        }
+    }
+
+    /// Rows needed for the table including its title line.
+    pub fn panel_height(&self) -> u16 {
+        self.count as u16 + 1
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect) {
+        let theme = theme::current();
+        let title = match &self.pending_kill {
+            Some(target) => format!("SIGTERM {} ({})? y/n", target.name, target.pid),
+            None => format!(
+                "Top by {}  s: sort  K: kill",
+                match self.sort {
+                    ProcessSort::Cpu => "CPU",
+                    ProcessSort::Memory => "MEM",
+                }
+            ),
+        };
+        let title_style = if self.pending_kill.is_some() {
+            Style::new().fg(theme.critical).bold()
+        } else {
+            Style::new().fg(theme.muted)
+        };
+
+        let lines: Vec<Line> = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(i, process)| {
+                let text = format!(
+                    "{:>7} {:<15.15} {:>5.1}% {:>7}",
+                    process.pid.as_u32(),
+                    process.name,
+                    process.cpu_usage,
+                    format_bytes(process.memory)
+                );
+                if i == self.selected {
+                    Line::styled(text, Style::new().fg(theme.fg).reversed())
+                } else {
+                    Line::styled(text, Style::new().fg(theme.muted))
+                }
+            })
+            .collect();
+
+        let block = Block::default()
+            .borders(Borders::NONE)
+            .title(title)
+            .title_style(title_style);
+        f.render_widget(Paragraph::new(lines).block(block), area);
+    }
+}
@@ -0,0 +1,21 @@
+use crate::glob::glob_match;
+use sysinfo::Component;
+
+/// Tried in order when no `cpu_sensor` is configured. Component labels are
+/// "<hwmon name> <sensor label>", e.g. "k10temp Tctl" or "coretemp Package id 0".
+const AUTO_CPU_SENSORS: [&str; 4] = ["*Tctl*", "*Package id 0*", "coretemp*", "k10temp*"];
+
+/// Index of the component used for the CPU temperature bar.
+pub fn find_cpu_sensor(components: &[Component], configured: Option<&str>) -> Option<usize> {
+    let position = |pattern: &str| {
+        components
+            .iter()
+            .position(|component| glob_match(pattern, component.label()))
+    };
+    match configured {
+        Some(pattern) => position(pattern),
+        None => AUTO_CPU_SENSORS
+            .iter()
+            .find_map(|pattern| position(pattern)),
+    }
+}
@@ -1,73 +1,327 @@
+use crate::color::Gradient;
+use crate::config::IconStyle;
 use crate::fontloader;
 use crate::logger::Logger;
-use crate::widgets::GJWidget;
-use crate::{config::WeatherConfig, fontloader::load_font_by_name_or_err};
-use std::env;
+use crate::theme;
+use crate::widgets::{GJWidget, WidgetErrors};
+use crate::{config::WeatherConfig, fontloader::load_font};
 use std::process::Command;
 
-use figlet_rs::FIGfont;
+use crate::fontloader::FontChain;
 use ratatui::Frame;
 use ratatui::layout::Rect;
+use ratatui::text::{Line, Span, Text};
 use ratatui::{
     layout::Alignment,
-    style::{Color, Style},
+    style::{Modifier, Style},
     widgets::{Block, Borders, Paragraph},
 };
 
+/// Plain-text condition symbol, temperature, wind, moon phase and precipitation.
+const WTTR_FORMAT: &str = "%x|%t|%w|%m|%p";
+
+/// Weather conditions as reported by wttr.in's plain-text symbols (`%x`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Condition {
+    Sunny,
+    PartlyCloudy,
+    Cloudy,
+    VeryCloudy,
+    Fog,
+    LightShowers,
+    LightRain,
+    HeavyShowers,
+    HeavyRain,
+    LightSleet,
+    LightSleetShowers,
+    LightSnow,
+    LightSnowShowers,
+    HeavySnow,
+    HeavySnowShowers,
+    ThunderyShowers,
+    ThunderyHeavyRain,
+    ThunderySnowShowers,
+    Unknown,
+}
+
+impl Condition {
+    fn from_code(code: &str) -> Self {
+        match code.trim() {
+            "o" => Condition::Sunny,
+            "m" => Condition::PartlyCloudy,
+            "mm" => Condition::Cloudy,
+            "mmm" => Condition::VeryCloudy,
+            "=" => Condition::Fog,
+            "." => Condition::LightShowers,
+            "/" => Condition::LightRain,
+            "//" => Condition::HeavyShowers,
+            "///" => Condition::HeavyRain,
+            "x" => Condition::LightSleet,
+            "x/" => Condition::LightSleetShowers,
+            "*" => Condition::LightSnow,
+            "*/" => Condition::LightSnowShowers,
+            "**" => Condition::HeavySnow,
+            "*/*" => Condition::HeavySnowShowers,
+            "!/" => Condition::ThunderyShowers,
+            "/!/" => Condition::ThunderyHeavyRain,
+            "*!*" => Condition::ThunderySnowShowers,
+            _ => Condition::Unknown,
+        }
+    }
+
+    fn is_severe(self) -> bool {
+        matches!(
+            self,
+            Condition::HeavyRain
+                | Condition::HeavySnow
+                | Condition::HeavySnowShowers
+                | Condition::ThunderyShowers
+                | Condition::ThunderyHeavyRain
+                | Condition::ThunderySnowShowers
+        )
+    }
+
+    fn icon(self, icons: IconStyle) -> &'static str {
+        match icons {
+            IconStyle::Nerd => self.nerd_icon(),
+            IconStyle::Emoji => self.emoji_icon(),
+            IconStyle::Ascii => self.ascii_icon(),
+        }
+    }
+
+    fn nerd_icon(self) -> &'static str {
+        match self {
+            Condition::Sunny => "\u{e30d}",
+            Condition::PartlyCloudy => "\u{e302}",
+            Condition::Cloudy | Condition::VeryCloudy => "\u{e312}",
+            Condition::Fog => "\u{e313}",
+            Condition::LightShowers | Condition::HeavyShowers => "\u{e319}",
+            Condition::LightRain | Condition::HeavyRain => "\u{e318}",
+            Condition::LightSleet | Condition::LightSleetShowers => "\u{e3ad}",
+            Condition::LightSnow
+            | Condition::LightSnowShowers
+            | Condition::HeavySnow
+            | Condition::HeavySnowShowers => "\u{e31a}",
+            Condition::ThunderyShowers | Condition::ThunderySnowShowers => "\u{e31c}",
+            Condition::ThunderyHeavyRain => "\u{e31d}",
+            Condition::Unknown => "\u{e374}",
+        }
+    }
+
+    fn emoji_icon(self) -> &'static str {
+        match self {
+            Condition::Sunny => "☀️",
+            Condition::PartlyCloudy => "⛅",
+            Condition::Cloudy | Condition::VeryCloudy => "☁️",
+            Condition::Fog => "🌫",
+            Condition::LightShowers | Condition::HeavyShowers => "🌦",
+            Condition::LightRain | Condition::HeavyRain => "🌧",
+            Condition::LightSleet | Condition::LightSleetShowers => "🌧",
+            Condition::LightSnow | Condition::LightSnowShowers => "🌨",
+            Condition::HeavySnow | Condition::HeavySnowShowers => "❄️",
+            Condition::ThunderyShowers | Condition::ThunderySnowShowers => "🌩",
+            Condition::ThunderyHeavyRain => "⛈",
+            Condition::Unknown => "✨",
+        }
+    }
+
+    /// Figlet fonts only cover ASCII, so font mode uses wttr.in's own plain symbols.
+    fn ascii_icon(self) -> &'static str {
+        match self {
+            Condition::Sunny => "o",
+            Condition::PartlyCloudy => "m",
+            Condition::Cloudy => "mm",
+            Condition::VeryCloudy => "mmm",
+            Condition::Fog => "=",
+            Condition::LightShowers => ".",
+            Condition::LightRain => "/",
+            Condition::HeavyShowers => "//",
+            Condition::HeavyRain => "///",
+            Condition::LightSleet => "x",
+            Condition::LightSleetShowers => "x/",
+            Condition::LightSnow => "*",
+            Condition::LightSnowShowers => "*/",
+            Condition::HeavySnow => "**",
+            Condition::HeavySnowShowers => "*/*",
+            Condition::ThunderyShowers => "!/",
+            Condition::ThunderyHeavyRain => "/!/",
+            Condition::ThunderySnowShowers => "*!*",
+            Condition::Unknown => "?",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct WeatherReport {
+    condition: Condition,
+    temperature: String,
+    degrees: Option<f32>,
+    details: String,
+}
+
+impl WeatherReport {
+    fn parse(raw: &str) -> Option<Self> {
+        let fields: Vec<&str> = raw.split('|').map(|f| f.trim()).collect();
+        if fields.len() < 2 {
+            return None;
+        }
+        let temperature = fields[1].to_string();
+        let degrees = temperature
+            .trim_end_matches(|c: char| !c.is_ascii_digit())
+            .parse::<f32>()
+            .ok();
+
+        Some(Self {
+            condition: Condition::from_code(fields[0]),
+            temperature,
+            degrees,
+            details: fields[2..].join(" "),
+        })
+    }
+}
+
+/// Percent-encodes everything but RFC 3986 unreserved characters.
+fn url_encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+fn wttr_url(location: &str) -> String {
+    format!(
+        "https://wttr.in/{}?format={}",
+        url_encode(location),
+        url_encode(WTTR_FORMAT)
+    )
+}
+
 pub struct WeatherWidget {
     config: WeatherConfig,
-    font: Option<FIGfont>,
+    font: Option<FontChain>,
     state: String,
+    report: Option<WeatherReport>,
+    gradient: Gradient,
+    errors: WidgetErrors,
     logger: &'static Logger,
 }
 
 impl WeatherWidget {
     pub fn new(config: WeatherConfig, logger: &'static Logger) -> Self {
-        let font = load_font_by_name_or_err(&config.font);
+        let font = load_font(&config.font, logger);
+        let gradient = Gradient::from_strings(&config.temp_gradient);
         Self {
             state: "Loading".to_string(),
+            report: None,
             config,
             font,
+            gradient,
+            errors: WidgetErrors::default(),
             logger,
         }
     }
 
-    fn fetch_weather(location: String, logger: &'static Logger) -> String {
-        let output = Command::new("sh")
-            .arg("-c")
-            .arg(format!(
-                "curl -s wttr.in/{}?format=%c+%t+%w+%m+%p",
-                location
-            ))
+    fn fetch_weather(location: String) -> Result<String, String> {
+        let output = Command::new("curl")
+            .arg("-sS")
+            .arg(wttr_url(&location))
             .output()
-            .expect("Failed to run weather command");
+            .map_err(|e| format!("Failed to run curl: {}", e))?;
 
-        match output {
-            output if output.status.success() => {
-                String::from_utf8_lossy(&output.stdout).trim().to_string()
-            }
-            output => {
-                let error_message = format!(
-                    "Weather script error: {}\nscript path: {}",
-                    String::from_utf8_lossy(&output.stderr),
-                    env::current_dir().unwrap().to_string_lossy(),
-                );
-                logger.error(&error_message);
-                error_message
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        } else {
+            Err(format!(
+                "Weather request failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+        }
+    }
+
+    fn temperature_style(&self, report: &WeatherReport) -> Style {
+        let base = Style::default().add_modifier(Modifier::BOLD);
+        match report.degrees {
+            Some(degrees) if !self.gradient.is_empty() => {
+                let (cold, hot) = self.config.temp_range;
+                let t = if hot > cold {
+                    (degrees - cold) / (hot - cold)
+                } else {
+                    0.5
+                };
+                base.fg(self.gradient.at(t))
             }
+            _ => base.fg(theme::current().muted),
+        }
+    }
+
+    fn condition_style(condition: Condition) -> Style {
+        let theme = theme::current();
+        if condition.is_severe() {
+            Style::default()
+                .fg(theme.critical)
+                .add_modifier(Modifier::BOLD | Modifier::SLOW_BLINK)
+        } else {
+            Style::default().fg(theme.muted)
         }
     }
 }
 impl GJWidget for WeatherWidget {
+    fn error(&self) -> Option<String> {
+        self.errors.current()
+    }
+
     fn poll(&mut self) {
-        self.state = Self::fetch_weather(self.config.location.clone(), self.logger);
+        self.state = match Self::fetch_weather(self.config.location.clone()) {
+            Ok(state) => {
+                self.errors.clear("fetch");
+                state
+            }
+            Err(err) => {
+                self.logger.error(&err);
+                self.errors.set("fetch", &err);
+                err
+            }
+        };
+        self.report = WeatherReport::parse(&self.state);
     }
 
     fn render(&self, f: &mut Frame, area: Rect) {
-        let style = Style::default().fg(Color::DarkGray);
+        let style = Style::default().fg(theme::current().muted);
         //.add_modifier(Modifier::ITALIC);
 
-        let text = fontloader::to_styled_text(&self.font, &self.state, style);
+        let headline = self
+            .report
+            .as_ref()
+            .map(|report| format!("{} {}", report.condition.ascii_icon(), report.temperature));
+
+        let text = match (&self.report, &headline) {
+            (Some(report), Some(headline)) if self.font.is_some() => {
+                let headline_style = if report.condition.is_severe() {
+                    Self::condition_style(report.condition)
+                } else {
+                    self.temperature_style(report)
+                };
+                let mut text =
+                    fontloader::to_styled_text(&self.font, headline, headline_style, area.width);
+                text.lines.push(Line::styled(report.details.clone(), style));
+                text
+            }
+            (Some(report), _) => Text::from(Line::from(vec![
+                Span::styled(
+                    report.condition.icon(self.config.icons),
+                    Self::condition_style(report.condition),
+                ),
+                Span::raw(" "),
+                Span::styled(report.temperature.clone(), self.temperature_style(report)),
+                Span::raw(" "),
+                Span::styled(report.details.clone(), style),
+            ])),
+            _ => fontloader::to_styled_text(&self.font, &self.state, style, area.width),
+        };
 
         let paragraph = Paragraph::new(text)
             .alignment(Alignment::Center)
@@ -76,3 +330,43 @@ impl GJWidget for WeatherWidget {
         f.render_widget(paragraph, area);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_condition_codes() {
+        assert_eq!(Condition::from_code("o"), Condition::Sunny);
+        assert_eq!(Condition::from_code(" mmm "), Condition::VeryCloudy);
+        assert_eq!(Condition::from_code("*/*"), Condition::HeavySnowShowers);
+        assert_eq!(Condition::from_code("/!/"), Condition::ThunderyHeavyRain);
+        assert_eq!(Condition::from_code("?"), Condition::Unknown);
+        assert!(Condition::from_code("///").is_severe());
+        assert!(!Condition::from_code("/").is_severe());
+    }
+
+    #[test]
+    fn parses_reports() {
+        let report = WeatherReport::parse("mm|-3°C|↙4km/h|🌖|0.1mm").unwrap();
+        assert_eq!(report.condition, Condition::Cloudy);
+        assert_eq!(report.temperature, "-3°C");
+        assert_eq!(report.degrees, Some(-3.0));
+        assert_eq!(report.details, "↙4km/h 🌖 0.1mm");
+
+        let report = WeatherReport::parse("o|+21°C").unwrap();
+        assert_eq!(report.degrees, Some(21.0));
+        assert_eq!(report.details, "");
+
+        assert!(WeatherReport::parse("Unknown location").is_none());
+    }
+
+    #[test]
+    fn encodes_location_in_url() {
+        assert_eq!(
+            wttr_url("St John's"),
+            "https://wttr.in/St%20John%27s?format=%25x%7C%25t%7C%25w%7C%25m%7C%25p"
+        );
+        assert_eq!(url_encode("Hyvinkää"), "Hyvink%C3%A4%C3%A4");
+    }
+}
@@ -1,6 +1,6 @@
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Rect},
-    style::{Color, Style},
+    style::Style,
     widgets::{Block, BorderType, Borders, Padding, Paragraph},
 };
 use std::sync::mpsc::Sender;
@@ -12,12 +12,12 @@ use std::{
     path::PathBuf,
     process::Command,
     str::FromStr,
-    sync::mpsc,
+    sync::{Arc, Mutex, mpsc},
     time::Duration,
 };
 
-use super::GJWidget;
-use crate::{config::WorkspacesConfig, logger::Logger};
+use super::{GJWidget, WidgetErrors};
+use crate::{config::WorkspacesConfig, logger::Logger, theme};
 
 #[derive(Debug, Clone)]
 pub struct Workspace {
@@ -33,44 +33,54 @@ pub struct ActiveWorkspace {
 }
 
 pub struct WorkspacesWidget {
-    pub config: WorkspacesConfig,
     pub workspaces: Vec<Workspace>,
     connected: bool,
     tx_workspace: Option<std::sync::mpsc::Sender<Vec<Workspace>>>,
     rx_workspace: Option<std::sync::mpsc::Receiver<Vec<Workspace>>>,
     rx_event: Option<std::sync::mpsc::Receiver<()>>,
+    /// Shared with the socket thread, which reports connection failures.
+    errors: Arc<Mutex<WidgetErrors>>,
     logger: &'static Logger,
 }
 
 impl WorkspacesWidget {
-    pub fn new(config: WorkspacesConfig, logger: &'static Logger) -> Self {
+    pub fn new(_config: WorkspacesConfig, logger: &'static Logger) -> Self {
         let (tx_workspace, rx_workspace) = mpsc::channel::<Vec<Workspace>>();
         Self {
-            config,
             workspaces: Vec::new(),
             connected: false,
             tx_workspace: Some(tx_workspace),
             rx_workspace: Some(rx_workspace),
             rx_event: None,
+            errors: Arc::new(Mutex::new(WidgetErrors::default())),
             logger,
         }
     }
 
     fn handle_workspace_update(&mut self) {
-        if let Ok(workspaces) = self.fetch_workspaces() {
-            if let Ok(active_id_option) = self.fetch_active_workspace() {
-                let active_id = active_id_option.unwrap_or(-1);
-
-                self.workspaces = workspaces
-                    .into_iter()
-                    .map(|mut ws| {
-                        ws.active = ws.id == active_id;
-                        ws
-                    })
-                    .collect();
-            } else {
-                self.workspaces = workspaces;
+        let workspaces = match self.fetch_workspaces() {
+            Ok(workspaces) => {
+                self.errors.lock().unwrap().clear("fetch workspaces");
+                workspaces
+            }
+            Err(err) => {
+                self.logger.error(&err);
+                self.errors.lock().unwrap().set("fetch workspaces", &err);
+                return;
             }
+        };
+        if let Ok(active_id_option) = self.fetch_active_workspace() {
+            let active_id = active_id_option.unwrap_or(-1);
+
+            self.workspaces = workspaces
+                .into_iter()
+                .map(|mut ws| {
+                    ws.active = ws.id == active_id;
+                    ws
+                })
+                .collect();
+        } else {
+            self.workspaces = workspaces;
         }
     }
 
@@ -118,11 +128,16 @@ impl WorkspacesWidget {
 }
 
 impl GJWidget for WorkspacesWidget {
+    fn error(&self) -> Option<String> {
+        self.errors.lock().unwrap().current()
+    }
+
     fn render(&self, frame: &mut ratatui::Frame, area: ratatui::prelude::Rect) {
+        let theme = theme::current();
         let mut grouped: BTreeMap<u32, Vec<Workspace>> = BTreeMap::new();
         for ws in &self.workspaces {
             let group = if ws.id < 0 { 10 } else { ws.monitor_id };
-            grouped.entry(group as u32).or_default().push(ws.clone());
+            grouped.entry(group).or_default().push(ws.clone());
         }
         let column_width = 12;
 
@@ -146,16 +161,16 @@ impl GJWidget for WorkspacesWidget {
             let mut column_workspaces = column_workspaces.clone();
             column_workspaces.sort_by_key(|ws| ws.id);
             let column_area = columns[i];
-            let block_height = 3 as u16;
+            let block_height = 3_u16;
 
             for (j, ws) in column_workspaces.iter().enumerate() {
                 let block = Block::default()
                     .borders(Borders::ALL)
                     .border_type(BorderType::Thick)
                     .border_style(if ws.active {
-                        Style::default().fg(Color::Blue)
+                        Style::default().fg(theme.accent)
                     } else {
-                        Style::default().fg(Color::DarkGray)
+                        Style::default().fg(theme.border)
                     })
                     .title(if ws.id.to_string() != ws.name {
                         ws.name.clone()
@@ -184,24 +199,28 @@ impl GJWidget for WorkspacesWidget {
             self.rx_workspace = Some(workspace_rx);
 
             let tx_workspaces = self.tx_workspace.clone();
+            let errors = Arc::clone(&self.errors);
             let logger = self.logger;
 
             std::thread::spawn(move || {
                 let mut socket = HyprSocketWorker {
                     event_tx,
                     tx: tx_workspaces.expect("Workspace sender should be initialized"),
+                    errors,
                 };
                 if let Err(e) = socket.connect_hyprland_socket(logger) {
-                    logger.error(&format!("Socket error in thread: {}", e));
+                    let message = format!("Socket error in thread: {}", e);
+                    logger.error(&message);
+                    socket.errors.lock().unwrap().set("socket", &message);
                 }
             });
 
             std::thread::sleep(Duration::from_millis(100));
 
-            if let Some(rx) = &self.rx_workspace {
-                if let Ok(initial_workspaces) = rx.try_recv() {
-                    self.workspaces = initial_workspaces;
-                }
+            if let Some(rx) = &self.rx_workspace
+                && let Ok(initial_workspaces) = rx.try_recv()
+            {
+                self.workspaces = initial_workspaces;
             }
         }
 
@@ -217,6 +236,7 @@ impl GJWidget for WorkspacesWidget {
 struct HyprSocketWorker {
     event_tx: Sender<()>,
     tx: Sender<Vec<Workspace>>,
+    errors: Arc<Mutex<WidgetErrors>>,
 }
 
 trait HyprSocket {
@@ -241,19 +261,29 @@ impl HyprSocket for HyprSocketWorker {
         };
 
         let reader = BufReader::new(stream);
+        self.errors.lock().unwrap().clear("socket");
 
         if let Ok(workspaces) = self.set_workspaces(logger) {
+            self.errors.lock().unwrap().clear("fetch workspaces");
             let _ = self.tx.send(workspaces);
         } else {
-            logger.error("HyprSocketWorker initial workspace fetch failed.");
+            let message = "HyprSocketWorker initial workspace fetch failed.";
+            logger.error(message);
+            self.errors.lock().unwrap().set("fetch workspaces", message);
         }
 
         for line in reader.lines() {
             match line {
                 Ok(line) => {
+                    logger.trace(&format!("Hyprland event: {}", line));
+                    self.errors.lock().unwrap().clear("socket");
                     self.handle_socket_event(&line);
                 }
-                Err(e) => logger.error(&format!("Failed to read line from socket: {}", e)),
+                Err(e) => {
+                    let message = format!("Failed to read line from socket: {}", e);
+                    logger.error(&message);
+                    self.errors.lock().unwrap().set("socket", &message);
+                }
             }
         }
         Ok(())